@@ -0,0 +1,65 @@
+use std::fmt::Write;
+use std::path::Path;
+
+/// The year of the examples kept directly in `inputs/`, from before there were year directories.
+const LEGACY_YEAR: u16 = 2024;
+
+/// Generates one test per `inputs/<year>/<day>_<name>.txt` (or legacy `inputs/<day>_<name>.txt`) that has a
+/// sibling `.expected` file, so adding an example only takes dropping the two files into `inputs/`.
+/// Examples whose `.expected` file has no answers yet are generated as ignored tests.
+fn main() {
+    println!("cargo:rerun-if-changed=inputs");
+
+    // Year directories first, so their copy wins over an example still left in `inputs/` after moving it
+    let mut examples = Vec::new();
+    for path in read_dir(Path::new("inputs")) {
+        let year = path.file_name().and_then(|name| name.to_str()).and_then(|name| name.parse::<u16>().ok());
+        if let (true, Some(year)) = (path.is_dir(), year) {
+            println!("cargo:rerun-if-changed={}", path.display());
+            collect_examples(&path, year, &mut examples);
+        }
+    }
+    collect_examples(Path::new("inputs"), LEGACY_YEAR, &mut examples);
+    let mut seen = std::collections::HashSet::new();
+    examples.retain(|(year, day, name, _)| seen.insert((*year, *day, name.clone())));
+    examples.sort();
+
+    let mut out = String::new();
+    for (year, day, name, answered) in examples {
+        let ident: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+        let ignore = if answered { "" } else { "#[ignore = \"no expected answers yet\"]\n" };
+        writeln!(
+            out,
+            "#[test]\n{}fn y{}_day{}_{}() {{\n    check_example({}, {}, {:?});\n}}\n",
+            ignore, year, day, ident, year, day, name
+        )
+        .unwrap();
+    }
+
+    let dest = Path::new(&std::env::var("OUT_DIR").unwrap()).join("examples.rs");
+    std::fs::write(dest, out).unwrap();
+}
+
+/// The paths in `dir`, none when it does not exist, e.g. in a checkout without inputs.
+fn read_dir(dir: &Path) -> Vec<std::path::PathBuf> {
+    match std::fs::read_dir(dir) {
+        Ok(entries) => entries.map(|entry| entry.unwrap().path()).collect(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => panic!("Could not read {}: {}", dir.display(), error),
+    }
+}
+
+fn collect_examples(dir: &Path, year: u16, examples: &mut Vec<(u16, u8, String, bool)>) {
+    for path in read_dir(dir) {
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+        let Ok(expected) = std::fs::read_to_string(path.with_extension("expected")) else { continue };
+        let answered = expected.lines().map(str::trim).any(|line| line.starts_with("part"));
+
+        let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+        let Some((day, name)) = stem.split_once('_') else { continue };
+        let Ok(day) = day.parse::<u8>() else { continue };
+        examples.push((year, day, name.to_string(), answered));
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::{Display, Formatter};
+
+/// Heap use of one stage of a day, counted on the thread that ran it. Threads a solver spawns itself are not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Allocations and reallocations
+    pub allocations: u64,
+    /// Bytes requested by those allocations
+    pub bytes: u64,
+    /// How far the heap grew above where it was when the stage started
+    pub peak: u64,
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocs, {}, peak {}", self.allocations, format_bytes(self.bytes), format_bytes(self.peak))
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    #[derive(Clone, Copy)]
+    pub(super) struct Counters {
+        pub allocations: u64,
+        pub bytes: u64,
+        /// Bytes allocated minus bytes freed by this thread, negative if it frees what others allocated
+        pub current: i64,
+        pub peak: i64,
+    }
+
+    thread_local! {
+        pub(super) static COUNTERS: Cell<Counters> =
+            const { Cell::new(Counters { allocations: 0, bytes: 0, current: 0, peak: 0 }) };
+    }
+
+    fn update(f: impl FnOnce(&mut Counters)) {
+        // While a thread is torn down its counters are gone, and nobody is looking at them anymore
+        let _ = COUNTERS.try_with(|counters| {
+            let mut value = counters.get();
+            f(&mut value);
+            counters.set(value);
+        });
+    }
+
+    fn allocated(bytes: usize, growth: i64) {
+        update(|c| {
+            c.allocations += 1;
+            c.bytes += bytes as u64;
+            c.current += growth;
+            c.peak = c.peak.max(c.current);
+        });
+    }
+
+    /// The system allocator, counting what each thread allocates.
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size(), layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size(), layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            update(|c| c.current -= layout.size() as i64);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                allocated(new_size, new_size as i64 - layout.size() as i64);
+            }
+            new
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+}
+
+/// Runs `f` and counts what it allocates on this thread, or returns no stats without the `alloc-stats` feature.
+#[cfg(feature = "alloc-stats")]
+pub fn track<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    use counting::{Counters, COUNTERS};
+
+    let start = COUNTERS.with(|c| c.get());
+    COUNTERS.with(|c| c.set(Counters { peak: start.current, ..start }));
+    let result = f();
+    let end = COUNTERS.with(|c| c.get());
+    // An enclosing `track` still needs its own peak
+    COUNTERS.with(|c| c.set(Counters { peak: end.peak.max(start.peak), ..end }));
+
+    let stats = AllocStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.current).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+pub fn track<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = track(|| {
+            let big = vec![0u8; 4096];
+            drop(big);
+            let mut small = Vec::with_capacity(10);
+            small.push(1u64);
+            small
+        });
+        let stats = stats.unwrap();
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 4096 + 80);
+        assert_eq!(stats.peak, 4096);
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn nested_peaks() {
+        let ((_, inner), outer) = track(|| {
+            let big = vec![0u8; 8192];
+            drop(big);
+            track(|| vec![0u8; 100])
+        });
+        assert_eq!(inner.unwrap().peak, 100);
+        assert_eq!(outer.unwrap().peak, 8192);
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    #[test]
+    fn disabled() {
+        assert_eq!(track(|| 1), (1, None));
+    }
+}
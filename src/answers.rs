@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context};
+use crate::registry::LEGACY_YEAR;
+use crate::runner::{DayReport, PartReport, ANSI_GREEN_PLUS, ANSI_RED_BANG};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Known-good answers for the real inputs, kept in a small TOML file:
+///
+/// ```toml
+/// [2024.day1]
+/// part1 = "1234"
+/// part2 = "5678"
+/// ```
+///
+/// Sections without a year (`[day1]`) are from before there were years and belong to [`LEGACY_YEAR`].
+pub struct AnswerStore {
+    path: PathBuf,
+    days: BTreeMap<(u16, u8), DayAnswers>,
+}
+
+/// Parses a `2024.day1` or legacy `day1` section name.
+fn parse_section(section: &str) -> Option<(u16, u8)> {
+    let (year, day) = match section.split_once('.') {
+        Some((year, day)) => (year.parse().ok()?, day),
+        None => (LEGACY_YEAR, section),
+    };
+    Some((year, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// Parses a double-quoted TOML basic string with the common escapes.
+pub(crate) fn parse_string(value: &str) -> anyhow::Result<String> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .context("Expected a quoted string")?;
+
+    let mut out = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('n') => out.push('\n'),
+                other => bail!("Unsupported escape sequence \\{}", other.map_or(String::new(), String::from)),
+            },
+            '"' => bail!("Unescaped quote in string"),
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+/// Parses a `part1 = "..."` / `part2 = "..."` line into `answers`.
+pub(crate) fn parse_answer(line: &str, answers: &mut DayAnswers) -> anyhow::Result<()> {
+    let (key, value) = line.split_once('=').context("Expected `key = \"value\"`")?;
+    let value = parse_string(value.trim())?;
+    match key.trim() {
+        "part1" => answers.part1 = Some(value),
+        "part2" => answers.part2 = Some(value),
+        other => bail!("Unknown key `{}`", other),
+    }
+    Ok(())
+}
+
+/// The expected answers of an example input and the parameters to solve it with.
+#[cfg(test)]
+pub struct Expected {
+    pub answers: DayAnswers,
+    pub params: crate::params::Params,
+}
+
+/// Parses the expected answers of an example input, stored next to it as `inputs/<day>_<name>.expected`.
+/// It uses the same `part1 = "..."` lines as the answers file, without sections; either part may be left out.
+/// Lines like `params.field = "11x7"` set a day parameter for this example.
+#[cfg(test)]
+pub fn parse_expected(content: &str) -> anyhow::Result<Expected> {
+    let mut answers = DayAnswers::default();
+    let mut params = crate::params::Params::default();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let context = || format!("line {}: `{}`", i + 1, line);
+        match line.strip_prefix("params.").and_then(|l| l.split_once('=')) {
+            Some((name, value)) => params = params.with(name.trim(), &parse_string(value.trim()).with_context(context)?),
+            None => parse_answer(line, &mut answers).with_context(context)?,
+        }
+    }
+    Ok(Expected { answers, params })
+}
+
+/// Quotes `value` as a TOML basic string, the reverse of [`parse_string`].
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+impl AnswerStore {
+    /// Loads the store at `path`. A missing file is an empty store.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(AnswerStore { path: path.to_path_buf(), days: BTreeMap::new() });
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read answers from {}", path.display()))?;
+        Self::parse(path, &content).with_context(|| format!("Invalid answers file {}", path.display()))
+    }
+
+    pub fn parse(path: &Path, content: &str) -> anyhow::Result<Self> {
+        let mut days = BTreeMap::new();
+        let mut current: Option<(u16, u8)> = None;
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let context = || format!("line {}: `{}`", i + 1, line);
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let key = parse_section(section.trim())
+                    .with_context(|| format!("Expected a [YYYY.dayN] section on {}", context()))?;
+                days.entry(key).or_insert_with(DayAnswers::default);
+                current = Some(key);
+                continue;
+            }
+
+            let key = current.with_context(|| format!("Answer outside of a [YYYY.dayN] section on {}", context()))?;
+            parse_answer(line, days.get_mut(&key).unwrap()).with_context(context)?;
+        }
+
+        Ok(AnswerStore { path: path.to_path_buf(), days })
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&DayAnswers> {
+        self.days.get(&(year, day))
+    }
+
+    /// Stores the successful answers of `report`, keeping previously recorded parts that were not run.
+    pub fn record(&mut self, report: &DayReport) {
+        let answers = self.days.entry((report.year, report.day)).or_default();
+        if let Some(PartReport { answer: Ok(answer), .. }) = &report.part1 {
+            answers.part1 = Some(answer.clone());
+        }
+        if let Some(PartReport { answer: Ok(answer), .. }) = &report.part2 {
+            answers.part2 = Some(answer.clone());
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        for ((year, day), answers) in &self.days {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            writeln!(out, "[{}.day{}]", year, day).unwrap();
+            if let Some(part1) = &answers.part1 {
+                writeln!(out, "part1 = {}", quote(part1)).unwrap();
+            }
+            if let Some(part2) = &answers.part2 {
+                writeln!(out, "part2 = {}", quote(part2)).unwrap();
+            }
+        }
+        out
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        std::fs::write(&self.path, self.to_toml())
+            .with_context(|| format!("Could not write answers to {}", self.path.display()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CheckOutcome {
+    Match,
+    Mismatch { expected: String, actual: String },
+    Unrecorded,
+    Failed(String),
+}
+
+/// Compares every part that ran in `report` against the recorded answers.
+pub fn check(report: &DayReport, store: &AnswerStore) -> Vec<(u8, CheckOutcome)> {
+    if let Some(error) = &report.parse_error {
+        return vec![(0, CheckOutcome::Failed(format!("{:#}", error)))];
+    }
+
+    let recorded = store.get(report.year, report.day).cloned().unwrap_or_default();
+    [(1, &report.part1, recorded.part1), (2, &report.part2, recorded.part2)]
+        .into_iter()
+        .filter_map(|(n, part, expected)| {
+            let outcome = match (&part.as_ref()?.answer, expected) {
+                (Err(error), _) => CheckOutcome::Failed(format!("{:#}", error)),
+                (Ok(_), None) => CheckOutcome::Unrecorded,
+                (Ok(actual), Some(expected)) if *actual == expected => CheckOutcome::Match,
+                (Ok(actual), Some(expected)) => CheckOutcome::Mismatch { expected, actual: actual.clone() },
+            };
+            Some((n, outcome))
+        })
+        .collect()
+}
+
+pub fn print_check(day: u8, outcomes: &[(u8, CheckOutcome)]) {
+    for (part, outcome) in outcomes {
+        let stage = if *part == 0 { "input".to_string() } else { format!("part {}", part) };
+        match outcome {
+            CheckOutcome::Match => println!("{} Day {} {}: ok", ANSI_GREEN_PLUS, day, stage),
+            CheckOutcome::Unrecorded => println!("[-] Day {} {}: no recorded answer", day, stage),
+            CheckOutcome::Mismatch { expected, actual } => println!(
+                "{} Day {} {}: expected {} but got {}",
+                ANSI_RED_BANG, day, stage, expected, actual
+            ),
+            CheckOutcome::Failed(error) => println!("{} Day {} {} failed: {}", ANSI_RED_BANG, day, stage, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    fn report(part1: &str, part2: Option<&str>) -> DayReport {
+        let part = |answer: &str| PartReport { answer: Ok(answer.to_string()), time: Duration::ZERO, alloc: None };
+        DayReport {
+            year: 2024,
+            day: 4,
+            parse_time: Duration::ZERO,
+            parse_alloc: None,
+            parse_error: None,
+            part1: Some(part(part1)),
+            part2: part2.map(part),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let content = "# comment\n[2023.day1]\npart1 = \"11\"\npart2 = \"31\"\n\n[2024.day4]\npart1 = \"a \\\"b\\\"\"\n";
+        let store = AnswerStore::parse(Path::new("answers.toml"), content).unwrap();
+        assert_eq!(store.get(2023, 1).unwrap().part2.as_deref(), Some("31"));
+        assert_eq!(store.get(2024, 1), None);
+        assert_eq!(store.get(2024, 4).unwrap().part1.as_deref(), Some("a \"b\""));
+        assert_eq!(store.get(2024, 4).unwrap().part2, None);
+
+        let again = AnswerStore::parse(Path::new("answers.toml"), &store.to_toml()).unwrap();
+        assert_eq!(again.days, store.days);
+    }
+
+    #[test]
+    fn legacy_sections_are_2024() {
+        let store = AnswerStore::parse(Path::new("a"), "[day4]\npart1 = \"18\"").unwrap();
+        assert_eq!(store.get(2024, 4).unwrap().part1.as_deref(), Some("18"));
+        assert!(store.to_toml().starts_with("[2024.day4]\n"));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(AnswerStore::parse(Path::new("a"), "part1 = \"1\"").is_err());
+        assert!(AnswerStore::parse(Path::new("a"), "[dayx]").is_err());
+        assert!(AnswerStore::parse(Path::new("a"), "[24x.day1]").is_err());
+        assert!(AnswerStore::parse(Path::new("a"), "[day1]\npart3 = \"1\"").is_err());
+        assert!(AnswerStore::parse(Path::new("a"), "[day1]\npart1 = 1").is_err());
+    }
+
+    #[test]
+    fn expected_file() {
+        let expected = parse_expected("part2 = \"9\"\nparams.field = \"11x7\"\n").unwrap();
+        assert_eq!(expected.answers, DayAnswers { part1: None, part2: Some("9".into()) });
+        assert_eq!(expected.params, crate::params::Params::default().with("field", "11x7"));
+        assert!(parse_expected("[day1]").is_err());
+    }
+
+    #[test]
+    fn record_keeps_unrun_parts() {
+        let mut store = AnswerStore::parse(Path::new("a"), "[day4]\npart2 = \"9\"").unwrap();
+        store.record(&report("18", None));
+        assert_eq!(store.get(2024, 4), Some(&DayAnswers { part1: Some("18".into()), part2: Some("9".into()) }));
+    }
+
+    #[test]
+    fn check_outcomes() {
+        let store = AnswerStore::parse(Path::new("a"), "[day4]\npart1 = \"18\"").unwrap();
+        assert_eq!(check(&report("18", Some("9")), &store), vec![(1, CheckOutcome::Match), (2, CheckOutcome::Unrecorded)]);
+        assert_eq!(
+            check(&report("17", None), &store),
+            vec![(1, CheckOutcome::Mismatch { expected: "18".into(), actual: "17".into() })],
+        );
+    }
+}
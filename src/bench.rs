@@ -0,0 +1,123 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::runner::ANSI_GREEN_PLUS;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "Cannot compute stats without samples");
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+
+        Stats { min: samples[0], median, mean, p95 }
+    }
+}
+
+/// Runs `routine` on a fresh value from `setup` for `warmup` untimed and `iterations` timed rounds.
+/// Only the routine itself is timed, so cloning inputs in `setup` does not skew the numbers.
+pub fn measure<I, R>(
+    warmup: usize,
+    iterations: usize,
+    mut setup: impl FnMut() -> I,
+    mut routine: impl FnMut(I) -> R,
+) -> Stats {
+    for _ in 0..warmup {
+        black_box(routine(setup()));
+    }
+
+    let samples = (0..iterations)
+        .map(|_| {
+            let input = setup();
+            let start = Instant::now();
+            let output = routine(input);
+            let elapsed = start.elapsed();
+            black_box(output);
+            elapsed
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+pub struct BenchReport {
+    pub day: u8,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+pub fn print_bench(report: &BenchReport) {
+    println!(
+        "{} Day {} ({} iterations, {} warmup)",
+        ANSI_GREEN_PLUS, report.day, report.iterations, report.warmup
+    );
+    println!("{:<6} | {:>10} | {:>10} | {:>10} | {:>10}", "Stage", "min", "median", "mean", "p95");
+    println!("{}", "-".repeat(6 + 4 * 13));
+    for (name, stats) in [("parse", Some(&report.parse)), ("part1", report.part1.as_ref()), ("part2", report.part2.as_ref())] {
+        if let Some(stats) = stats {
+            println!(
+                "{:<6} | {:>10} | {:>10} | {:>10} | {:>10}",
+                name,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.p95),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn stats_odd() {
+        let stats = Stats::from_samples(ms(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn stats_even() {
+        let stats = Stats::from_samples(ms(&[4, 1, 2, 3]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn stats_p95() {
+        let stats = Stats::from_samples(ms(&(1..=100).collect::<Vec<_>>()));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn measure_counts_iterations() {
+        let mut calls = 0;
+        measure(2, 5, || (), |_| calls += 1);
+        assert_eq!(calls, 7);
+    }
+}
@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Lets the runner stop a part that ran past `--timeout`.
+///
+/// Parts run on a worker thread that has its token installed, so long searches grab it with
+/// [`CancelToken::current`] and poll it, returning early once it is cancelled.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+thread_local! {
+    static CURRENT: RefCell<CancelToken> = RefCell::new(CancelToken::default());
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The token of the part running on this thread. Outside of a worker it is never cancelled.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Installs `token` as the token of this thread.
+    pub fn set_current(token: CancelToken) {
+        CURRENT.with(|current| *current.borrow_mut() = token);
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fails with [`Cancelled`] once the token has been cancelled.
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            return Err(Cancelled.into());
+        }
+        Ok(())
+    }
+}
+
+/// Returned by a part that noticed its token was cancelled.
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// The answer of a part that did not finish within the timeout.
+#[derive(Debug)]
+pub struct Timeout(pub Duration);
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+/// Parses `--timeout` as a (possibly fractional) number of seconds.
+pub fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let secs: f64 = arg.parse().map_err(|_| format!("expected a number of seconds, got `{}`", arg))?;
+    if secs <= 0.0 {
+        return Err("the timeout must be positive".to_string());
+    }
+    Duration::try_from_secs_f64(secs).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_shared() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(clone.check().is_ok());
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(clone.check().unwrap_err().is::<Cancelled>());
+    }
+
+    #[test]
+    fn current_is_per_thread() {
+        let token = CancelToken::new();
+        token.cancel();
+        std::thread::spawn(move || {
+            CancelToken::set_current(token);
+            assert!(CancelToken::current().is_cancelled());
+        })
+        .join()
+        .unwrap();
+        assert!(!CancelToken::current().is_cancelled());
+    }
+
+    #[test]
+    fn timeout_arg() {
+        assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
+}
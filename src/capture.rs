@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::fmt::{Arguments, Write};
+
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Prints like `print!`, but into the capture buffer of the current thread while one is active.
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::capture::write(format_args!($($arg)*))
+    };
+}
+
+/// Prints like `println!`, but into the capture buffer of the current thread while one is active.
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::capture::write(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::capture::write(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub fn write(args: Arguments) {
+    BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+        Some(buffer) => buffer.write_fmt(args).unwrap(),
+        None => print!("{}", args),
+    });
+}
+
+/// Like [`write`], but goes to stderr when nothing is capturing.
+pub fn write_err(args: Arguments) {
+    BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+        Some(buffer) => buffer.write_fmt(args).unwrap(),
+        None => eprint!("{}", args),
+    });
+}
+
+pub fn is_capturing() -> bool {
+    BUFFER.with(|buffer| buffer.borrow().is_some())
+}
+
+/// Runs `f`, collecting everything it prints with [`out!`] and [`outln!`] instead of writing it to stdout.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let previous = BUFFER.with(|buffer| buffer.replace(Some(String::new())));
+    let result = f();
+    let output = BUFFER.with(|buffer| buffer.replace(previous)).unwrap_or_default();
+    (result, output)
+}
+
+/// Wraps `f` to run on another thread while printing to wherever the current thread prints.
+/// Hand the captured output to [`forward`] on this thread once it is done.
+pub fn inherit<R>(f: impl FnOnce() -> R) -> impl FnOnce() -> (R, Option<String>) {
+    let capturing = is_capturing();
+    move || {
+        if capturing {
+            let (result, output) = capture(f);
+            (result, Some(output))
+        } else {
+            (f(), None)
+        }
+    }
+}
+
+/// Prints the output another thread captured through [`inherit`].
+pub fn forward(output: Option<String>) {
+    if let Some(output) = output {
+        write(format_args!("{}", output));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_nested() {
+        let ((_, inner), outer) = capture(|| {
+            outln!("outer {}", 1);
+            capture(|| out!("inner"))
+        });
+        assert_eq!(inner, "inner");
+        assert_eq!(outer, "outer 1\n");
+        assert!(!is_capturing());
+    }
+
+    #[test]
+    fn forwards_from_threads() {
+        let (_, output) = capture(|| {
+            let task = inherit(|| outln!("from a thread"));
+            let (_, output) = std::thread::spawn(task).join().unwrap();
+            forward(output);
+        });
+        assert_eq!(output, "from a thread\n");
+    }
+}
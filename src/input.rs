@@ -0,0 +1,87 @@
+use std::io::Read;
+use std::path::PathBuf;
+use anyhow::{bail, Context};
+use crate::registry::LEGACY_YEAR;
+
+/// Where the puzzle input for a day comes from.
+///
+/// Inputs live in `inputs/<year>/`. For [`LEGACY_YEAR`] they may also sit directly in `inputs/`, where they were
+/// kept before there were years; that file is used when the one in the year directory does not exist.
+pub enum InputSource {
+    /// `inputs/<year>/<day>.txt`
+    Default(u16, u8),
+    /// `inputs/<year>/<day>_<variant>.txt`, e.g. `8_test1_small`
+    Variant(u16, u8, String),
+    /// An explicit file path
+    Path(PathBuf),
+    /// Read everything from standard input
+    Stdin,
+}
+
+impl InputSource {
+    pub fn from_args(year: u16, day: u8, input: Option<&str>, variant: Option<&str>) -> Self {
+        match (input, variant) {
+            (Some("-"), _) => InputSource::Stdin,
+            (Some(path), _) => InputSource::Path(PathBuf::from(path)),
+            (None, Some(variant)) => InputSource::Variant(year, day, variant.to_string()),
+            (None, None) => InputSource::Default(year, day),
+        }
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            InputSource::Default(year, day) => Some(year_path(*year, &format!("{}.txt", day))),
+            InputSource::Variant(year, day, variant) => Some(year_path(*year, &format!("{}_{}.txt", day, variant))),
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path().is_none_or(|path| path.exists())
+    }
+
+    pub fn describe(&self) -> String {
+        match self.path() {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+
+    pub fn load(&self) -> anyhow::Result<String> {
+        if let (InputSource::Default(year, day), false) = (self, self.exists()) {
+            bail!("There is no input at {}, download it with `aoc2024 fetch --year {} --day {}`", self.describe(), year, day);
+        }
+
+        match self.path() {
+            Some(path) => std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read input file {}", path.display())),
+            None => {
+                let mut content = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut content)
+                    .context("Could not read input from stdin")?;
+                Ok(content)
+            }
+        }
+    }
+}
+
+/// `inputs/<year>/<file>`, or `inputs/<file>` if only that exists and belongs to `year`.
+fn year_path(year: u16, file: &str) -> PathBuf {
+    let path = PathBuf::from(format!("inputs/{}/{}", year, file));
+    let legacy = PathBuf::from(format!("inputs/{}", file));
+    if year == LEGACY_YEAR && !path.exists() && legacy.exists() {
+        return legacy;
+    }
+    path
+}
+
+/// Parses `content` line by line, tagging any error with the line number and its text.
+pub fn parse_lines<T>(content: &str, mut f: impl FnMut(usize, &str) -> anyhow::Result<T>) -> anyhow::Result<Vec<T>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(i, line).with_context(|| format!("Invalid input on line {}: `{}`", i + 1, line)))
+        .collect()
+}
@@ -0,0 +1,129 @@
+//! Advent of Code solutions, grouped by event year in [`years`], and the machinery to run them.
+//!
+//! [`solve`] answers a single part without printing anything. The `aoc2024` binary is a thin command line
+//! interface over the day descriptors in [`registry`] and the reports in [`runner`].
+
+use std::fmt::{Display, Formatter};
+use anyhow::{bail, Context};
+use crate::params::Params;
+use crate::runner::{Parts, RunOptions};
+#[cfg(test)]
+use crate::input::InputSource;
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod capture;
+pub mod input;
+pub mod log;
+pub mod output;
+pub mod parallel;
+pub mod params;
+pub mod registry;
+pub mod remote;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod utils;
+pub mod watch;
+pub mod years;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer(pub String);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Solves `part` (1 or 2) of `day` of the `year` event for `input` with the day's default parameters.
+pub fn solve(year: u16, day: u8, part: u8, input: &str) -> anyhow::Result<Answer> {
+    solve_with_params(year, day, part, input, &Params::default())
+}
+
+/// Like [`solve`], but overrides some of the day's parameters.
+pub fn solve_with_params(year: u16, day: u8, part: u8, input: &str, params: &Params) -> anyhow::Result<Answer> {
+    let descriptor = registry::find(year, day)?;
+    let parts = match part {
+        1 => Parts::One,
+        2 => Parts::Two,
+        _ => bail!("There is no part {}, only 1 and 2", part),
+    };
+
+    let params = params.resolve(day, descriptor.params)?;
+    let report = descriptor.solve(input.to_string(), &params, &RunOptions::new(parts), &mut |_, _| {});
+    if let Some(error) = report.parse_error {
+        return Err(error.context(format!("Day {} could not parse its input", day)));
+    }
+
+    let part_report = if part == 1 { report.part1 } else { report.part2 };
+    let answer = part_report.expect("the requested part always runs").answer;
+    answer.map(Answer).with_context(|| format!("Day {} part {} failed", day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_api() {
+        let input = include_str!("../inputs/1_test1.txt");
+        assert_eq!(solve(2024, 1, 1, input).unwrap(), Answer("11".to_string()));
+        assert!(solve(2024, 1, 3, input).is_err());
+        assert!(solve(2024, 99, 1, input).is_err());
+        assert!(solve(1999, 1, 1, input).is_err());
+        assert!(solve(2024, 2, 1, "1 x 3").is_err());
+    }
+
+    #[test]
+    fn solve_with_params_api() {
+        let input = include_str!("../inputs/11_test1.txt");
+        let params = Params::default().with("blinks", "1");
+        assert_eq!(solve_with_params(2024, 11, 1, input, &params).unwrap().to_string(), "3");
+        assert!(solve_with_params(2024, 11, 1, input, &Params::default().with("nope", "1")).is_err());
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    use super::*;
+    use crate::answers::parse_expected;
+
+    /// Runs one `inputs/<year>/<day>_<name>.txt` example against its `.expected` file.
+    fn check_example(year: u16, day: u8, name: &str) {
+        let source = InputSource::Variant(year, day, name.to_string());
+        let content = source.load().unwrap();
+        let expected_path = source.path().unwrap().with_extension("expected");
+        let expected = std::fs::read_to_string(&expected_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|e| parse_expected(&e))
+            .unwrap_or_else(|e| panic!("{}: {:#}", expected_path.display(), e));
+
+        let parts = match (&expected.answers.part1, &expected.answers.part2) {
+            (Some(_), Some(_)) => Parts::Both,
+            (Some(_), None) => Parts::One,
+            (None, Some(_)) => Parts::Two,
+            (None, None) => panic!("{} lists no answers", expected_path.display()),
+        };
+
+        let descriptor = registry::find(year, day).unwrap();
+        let params = expected.params.resolve(day, descriptor.params).unwrap();
+        let report = descriptor.solve(content, &params, &RunOptions::new(parts), &mut |_, _| {});
+        if let Some(error) = &report.parse_error {
+            panic!("Could not parse {}: {:#}", source.describe(), error);
+        }
+
+        for (n, part, expected) in [(1, &report.part1, &expected.answers.part1), (2, &report.part2, &expected.answers.part2)] {
+            let (Some(part), Some(expected)) = (part, expected) else { continue };
+            match &part.answer {
+                Ok(answer) => assert_eq!(answer, expected, "Part {} of {}", n, source.describe()),
+                Err(error) => panic!("Part {} of {} failed: {:#}", n, source.describe(), error),
+            }
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
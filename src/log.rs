@@ -0,0 +1,146 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::capture;
+
+/// How much the solvers tell about what they are doing, set with `-q` and `-v`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Only warnings
+    Quiet,
+    /// Notices and progress bars, the default
+    Info,
+    /// Intermediate results, `-v`
+    Debug,
+    /// Everything, including dumps of whole maps, `-vv`
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+impl Level {
+    /// The level for `-q` and the number of `-v` flags.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Level::Quiet,
+            (false, 0) => Level::Info,
+            (false, 1) => Level::Debug,
+            (false, _) => Level::Trace,
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Logs to stderr at the given level, or into the capture buffer while running in parallel.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::capture::write_err(format_args!("{}\n", format_args!($($arg)*)));
+        }
+    };
+}
+
+/// Shown even with `-q`.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Quiet, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+const BAR_WIDTH: usize = 30;
+
+/// A progress bar on stderr for long loops. It only draws on a terminal at the default level or above,
+/// and never while output is captured, so it stays out of pipes, JSON and parallel runs.
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    shown: Option<usize>,
+    visible: bool,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize) -> Self {
+        let visible = enabled(Level::Info) && !capture::is_capturing() && std::io::stderr().is_terminal();
+        Progress { label, total, shown: None, visible }
+    }
+
+    /// Marks `done` out of the total steps as finished, redrawing only when the percentage changes.
+    pub fn set(&mut self, done: usize) {
+        if !self.visible {
+            return;
+        }
+
+        let percent = (done.min(self.total) * 100).checked_div(self.total).unwrap_or(100);
+        if self.shown == Some(percent) {
+            return;
+        }
+        self.shown = Some(percent);
+
+        let filled = percent * BAR_WIDTH / 100;
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r{} [{}{}] {:>3}% ({}/{})",
+            self.label,
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            percent,
+            done,
+            self.total
+        );
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.visible && self.shown.is_some() {
+            // Clear the bar so it does not end up between the answers
+            let _ = write!(std::io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_from_flags() {
+        assert_eq!(Level::from_flags(false, 0), Level::Info);
+        assert_eq!(Level::from_flags(false, 1), Level::Debug);
+        assert_eq!(Level::from_flags(false, 5), Level::Trace);
+        assert_eq!(Level::from_flags(true, 2), Level::Quiet);
+        assert!(Level::Trace > Level::Debug && Level::Info > Level::Quiet);
+    }
+
+    #[test]
+    fn logs_are_captured() {
+        let (_, output) = capture::capture(|| {
+            crate::warn!("always {}", 1);
+            crate::trace!("not by default");
+        });
+        assert_eq!(output, "always 1\n");
+    }
+}
@@ -1,83 +1,389 @@
-#![feature(test)]
-
-use crate::runner::Aoc;
-use clap::Parser;
-
-extern crate test;
-
-mod runner;
-pub mod days;
-pub mod utils;
+use aoc2024::answers::{check, print_check, AnswerStore, CheckOutcome};
+use aoc2024::bench::print_bench;
+use aoc2024::cancel::parse_timeout;
+use aoc2024::capture::capture;
+use aoc2024::input::InputSource;
+use aoc2024::log::{set_level, Level};
+use aoc2024::output::{print_results, OutputFormat};
+use aoc2024::parallel::{job_count, map_parallel};
+use aoc2024::params::{parse_param, Params};
+use aoc2024::runner::{
+    print_parse_alloc, print_parse_error, print_part, print_summary, DayReport, PartReport, Parts, RunOptions, ANSI_GREEN_PLUS, ANSI_RED_BANG,
+};
+use aoc2024::registry::{self, DayDescriptor};
+use aoc2024::info;
+use aoc2024::remote::{fetch_input, Client, Config};
+use aoc2024::scaffold::NewDay;
+use aoc2024::submit::{submit_answer, History, Outcome};
+use aoc2024::watch::watch;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The event year; defaults to the latest one with solutions
+    #[arg(short, long)]
+    year: Option<u16>,
+
     /// The day to run
+    #[arg(short, long, required_unless_present_any = ["all", "list"])]
+    day: Option<u8>,
+
+    /// Run every implemented day of the year in order and print a summary
+    #[arg(short, long, conflicts_with = "day")]
+    all: bool,
+
+    /// List the implemented days with their titles, parts and parameters
+    #[arg(short, long, conflicts_with_all = ["day", "all"])]
+    list: bool,
+
+    /// Which part(s) to run; `both` skips parts that are interactive or unfinished
+    #[arg(short, long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+
+    /// Read the input from this file instead of `inputs/<year>/<day>.txt` (`-` for stdin)
+    #[arg(short, long, conflicts_with_all = ["all", "variant"])]
+    input: Option<String>,
+
+    /// Read the input from `inputs/<year>/<day>_<variant>.txt`, e.g. `test1_small`
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Set a day parameter, e.g. `--param field=101x103`; can be given several times
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Give up on a part after this many seconds and report it as timed out
+    #[arg(short, long, value_parser = parse_timeout, conflicts_with = "bench")]
+    timeout: Option<Duration>,
+
+    /// Run days, and both parts of each day, in parallel on this many threads (one per core if no number is given)
+    #[arg(short, long, requires = "all", conflicts_with = "bench", num_args = 0..=1, default_missing_value = "0")]
+    jobs: Option<usize>,
+
+    /// Show more of what the solvers are doing; repeat for even more
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Only show answers, warnings and errors
     #[arg(short, long)]
-    day: u8,
+    quiet: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Benchmark instead of solving once: time parse and parts over this many iterations
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "format")]
+    bench: Option<u32>,
+
+    /// Untimed iterations to run before benchmarking
+    #[arg(long, default_value_t = 3, requires = "bench")]
+    warmup: u32,
+
+    /// Compare the answers for the real input against the answers file and fail if any changed
+    #[arg(long, conflicts_with_all = ["input", "variant", "bench", "format", "record"])]
+    check: bool,
+
+    /// Save the answers for the real input to the answers file
+    #[arg(long, conflicts_with_all = ["input", "variant", "bench"])]
+    record: bool,
+
+    /// Keep running: re-run the day whenever one of its input files changes and restart when the binary is rebuilt
+    #[arg(short, long, conflicts_with_all = ["all", "bench", "check", "record", "format"])]
+    watch: bool,
+
+    /// Where answers are recorded for --check and --record
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create the solver module, registry entry and example input files for a new day
+    NewDay {
+        /// The day to create
+        day: u8,
+
+        /// The name of the solver type, e.g. `ChronospatialComputer`
+        #[arg(long)]
+        name: String,
+
+        /// The puzzle title; derived from the name by default
+        #[arg(long)]
+        title: Option<String>,
+
+        /// The event year; defaults to the latest one with solutions
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+
+    /// Download the puzzle input of a day to `inputs/<year>/<day>.txt`, unless it is already there
+    Fetch {
+        /// The day to download
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The event year; defaults to the latest one with solutions
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+
+    /// Solve a part for the real input and submit the answer, unless earlier submissions show it is wrong
+    Submit {
+        /// The day to submit
+        #[arg(short, long)]
+        day: u8,
+
+        /// The part to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// The event year; defaults to the latest one with solutions
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Where every submission and its outcome is kept
+        #[arg(long, default_value = "submissions.txt")]
+        history: PathBuf,
+
+        /// Where a correct answer is recorded for --check
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
 }
 
-fn load_content(name: String) -> String {
-    let path = format!("inputs/{}.txt", name);
-    std::fs::read_to_string(path).unwrap()
+fn check_report(report: &DayReport, store: &AnswerStore) -> bool {
+    let outcomes = check(report, store);
+    print_check(report.day, &outcomes);
+    outcomes.iter().all(|(_, o)| !matches!(o, CheckOutcome::Mismatch { .. } | CheckOutcome::Failed(_)))
 }
 
-macro_rules! matcher {
-    ({ $($key:literal => $module:ty, $name:ident, $exp1:literal, $exp2:literal),* $(,)? }) => {
-fn run_day(day: u8, content: String) {
-        match day {
-            $($key => {<$module as Aoc>::run(content)},)*
-            _ => panic!("Day not implemented"),
-        }
+/// Prints what a day printed while it ran in parallel, kept apart from the JSON or CSV on stdout.
+fn print_output(day: u8, output: &str, format: OutputFormat) {
+    if output.is_empty() {
+        return;
+    }
+
+    let output = format!("[-] Day {} output:\n{}", day, output);
+    if format == OutputFormat::Text {
+        print!("{}", output);
+    } else {
+        eprint!("{}", output);
+    }
 }
 
-        #[cfg(test)]
-        mod stages {
-            pub use super::*;
-            $(
-                #[cfg(test)]
-                mod $name {
-                    use super::*;
-                    #[test]
-                    fn part1() {
-                        let content = load_content(format!("{}_test1", $key));
-                        <$module as Aoc>::test_1(content, $exp1.to_string());
-                    }
-
-                    #[test]
-                    fn part2() {
-                        let content = load_content(format!("{}_test2", $key));
-                        <$module as Aoc>::test_2(content, $exp2.to_string());
-                    }
-                }
-            )*
+fn run_all(args: &Args, year: u16) -> anyhow::Result<()> {
+    let mut store = AnswerStore::load(&args.answers)?;
+    let mut tasks = Vec::new();
+    let mut all_ok = true;
+
+    if registry::days_of(year).next().is_none() {
+        anyhow::bail!("There are no solutions for {} (see --list)", year);
+    }
+
+    // Each day only gets the parameters it declares, but every parameter has to mean something to some day
+    let params = Params::new(args.params.clone());
+    if let Some(unknown) = params.names().find(|name| !registry::days_of(year).any(|d| d.params.iter().any(|p| p.name == *name))) {
+        anyhow::bail!("No day of {} has a parameter `{}`", year, unknown);
+    }
+
+    for descriptor in registry::days_of(year) {
+        let day = descriptor.day;
+        let source = InputSource::from_args(year, day, None, args.variant.as_deref());
+        if !source.exists() {
+            info!("[-] Day {}: no input at {}, skipping", day, source.describe());
+            continue;
+        }
+
+        let content = source.load()?;
+        let params = params.only(descriptor.params).resolve(day, descriptor.params)?;
+        let parts = descriptor.parts_for(args.part);
+        if let Some(iterations) = args.bench {
+            print_bench(&descriptor.bench(content, &params, parts, args.warmup as usize, iterations as usize)?);
+            continue;
         }
+
+        tasks.push((descriptor, content, params));
+    }
+
+    let solve = |(descriptor, content, params): (&DayDescriptor, String, Params)| {
+        let options = RunOptions {
+            parts: descriptor.parts_for(args.part),
+            timeout: args.timeout,
+            parallel: args.jobs.is_some(),
+        };
+        descriptor.solve(content, &params, &options, &mut |_, _| {})
     };
+    let start = Instant::now();
+    let reports = match args.jobs {
+        Some(jobs) => map_parallel(tasks, job_count(jobs), |task| capture(|| solve(task)))
+            .into_iter()
+            .map(|(report, output)| {
+                print_output(report.day, &output, args.format);
+                report
+            })
+            .collect(),
+        None => tasks.into_iter().map(solve).collect::<Vec<_>>(),
+    };
+
+    for report in &reports {
+        if args.check {
+            all_ok &= check_report(report, &store);
+        }
+        if args.record {
+            store.record(report);
+        }
+    }
+
+    if args.record {
+        store.save()?;
+    }
+
+    if args.check {
+        if !all_ok {
+            anyhow::bail!("Some answers changed, see above");
+        }
+    } else if args.bench.is_none() {
+        if args.format == OutputFormat::Text {
+            print_summary(&reports);
+            if let Some(jobs) = args.jobs {
+                println!("{} Wall time {:.2?} on {} threads", ANSI_GREEN_PLUS, start.elapsed(), job_count(jobs));
+            }
+        } else {
+            let results: Vec<_> = reports.iter().flat_map(|r| r.results()).collect();
+            print_results(args.format, &results);
+        }
+    }
+    Ok(())
+}
+
+fn new_day(year: u16, day: u8, name: &str, title: Option<&str>) -> anyhow::Result<()> {
+    let new_day = NewDay::new(year, day, name, title)?;
+    for path in new_day.create(Path::new("."))? {
+        println!("{} Wrote {}", ANSI_GREEN_PLUS, path.display());
+    }
+    println!("[-] Paste the example into inputs/{}/{}_test1.txt and its answers into the .expected file next to it", year, day);
+    Ok(())
+}
+
+fn fetch(year: u16, day: u8) -> anyhow::Result<()> {
+    let path = InputSource::Default(year, day).path().unwrap();
+    let client = Client::new(&Config::load()?)?;
+    if fetch_input(&client, year, day, &path)? {
+        println!("{} Downloaded day {} to {}", ANSI_GREEN_PLUS, day, path.display());
+    }
+    Ok(())
 }
 
+fn submit(year: u16, day: u8, part: u8, history: &Path, answers: &Path) -> anyhow::Result<()> {
+    let descriptor = registry::find(year, day)?;
+    let content = InputSource::Default(year, day).load()?;
+    let params = Params::default().resolve(day, descriptor.params)?;
+    let parts = if part == 1 { Parts::One } else { Parts::Two };
+    let report = descriptor.solve(content, &params, &RunOptions::new(parts), &mut |n, part| print_part(day, n, part));
+    if let Some(error) = report.parse_error {
+        return Err(error.context(format!("Day {} could not parse its input", day)));
+    }
+    let answer = match if part == 1 { &report.part1 } else { &report.part2 } {
+        Some(PartReport { answer: Ok(answer), .. }) => answer.clone(),
+        _ => anyhow::bail!("Day {} part {} has no answer to submit", day, part),
+    };
+
+    let client = Client::new(&Config::load()?)?;
+    let mut history = History::load(history)?;
+    let (outcome, message) = submit_answer(&client, &mut history, year, day, part, &answer)?;
+    match outcome {
+        Outcome::Correct => {
+            println!("{} {} is correct! {}", ANSI_GREEN_PLUS, answer, message);
+            let mut store = AnswerStore::load(answers)?;
+            store.record(&report);
+            store.save()?;
+        }
+        Outcome::Unknown => println!("[-] Unexpected response: {}", message),
+        outcome => println!("{} {} was not accepted ({}): {}", ANSI_RED_BANG, answer, outcome, message),
+    }
+    Ok(())
+}
 
-matcher!({
-        1 => days::dec1::HistorianHysteria, dec1, "11", "31",
-        2 => days::dec2::RedNosedReports, dec2, "2", "4",
-        3 => days::dec3::MullItOver, dec3, "161", "48",
-        4 => days::dec4::CeresSearch, dec4, "18", "9",
-        5 => days::dec5::PrintQueue, dec5, "143", "123",
-        6 => days::dec6::GuardGallivant, dec6, "41", "5",
-        7 => days::dec7::BridgeRepair, dec7, "3749", "11387",
-        8 => days::dec8::ResonantCollinearity, dec8, "14", "34",
-        9 => days::dec9::DiskFragmenter, dec9, "1928", "2858",
-        10 => days::dec10::HoofIt, dec10, "36", "81",
-        11 => days::dec11::PlutonianPebbles, dec11, "55312", "149161030616311",
-        12 => days::dec12::GardenGroups, dec12, "140", "368",
-        13 => days::dec13::ClawContraption, dec13, "480", "875318608908",
-        14 => days::dec14::RestroomRedoubt, dec14, "12", "0",
-        15 => days::dec15::WarehouseWoes, dec15, "10092", "0",
-        16 => days::dec16::ReindeerMaze, dec16, "7036", "45",
-});
-
-
-fn main() {
+fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let content = load_content(args.day.to_string());
-    run_day(args.day, content);
+    match &args.command {
+        Some(Command::NewDay { day, name, title, year }) => {
+            return new_day(year.unwrap_or_else(registry::latest_year), *day, name, title.as_deref());
+        }
+        Some(Command::Fetch { day, year }) => return fetch(year.unwrap_or_else(registry::latest_year), *day),
+        Some(Command::Submit { day, part, year, history, answers }) => {
+            return submit(year.unwrap_or_else(registry::latest_year), *day, *part, history, answers);
+        }
+        None => {}
+    }
+    // Machine-readable output stays free of progress and debug noise
+    let level = Level::from_flags(args.quiet, args.verbose);
+    set_level(if args.format == OutputFormat::Text { level } else { Level::Quiet });
+
+    if args.list {
+        registry::print_list(args.year);
+        return Ok(());
+    }
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    if args.all {
+        return run_all(&args, year);
+    }
+
+    let day = args.day.unwrap();
+    let descriptor = registry::find(year, day)?;
+    let parts = descriptor.parts_for(args.part);
+    let options = RunOptions { timeout: args.timeout, ..RunOptions::new(parts) };
+    let params = Params::new(args.params.clone()).resolve(day, descriptor.params)?;
+    let source = InputSource::from_args(year, day, args.input.as_deref(), args.variant.as_deref());
+    if args.watch {
+        if matches!(source, InputSource::Stdin) {
+            anyhow::bail!("--watch needs an input file, not stdin");
+        }
+        return watch(year, day, &source, || Ok(descriptor.solve(source.load()?, &params, &options, &mut |_, _| {})));
+    }
+
+    let content = source.load()?;
+    if let Some(iterations) = args.bench {
+        print_bench(&descriptor.bench(content, &params, parts, args.warmup as usize, iterations as usize)?);
+        return Ok(());
+    }
+
+    if args.check {
+        let store = AnswerStore::load(&args.answers)?;
+        if !check_report(&descriptor.solve(content, &params, &options, &mut |_, _| {}), &store) {
+            anyhow::bail!("Day {} no longer produces the recorded answers", day);
+        }
+        return Ok(());
+    }
+
+    let report = if args.format == OutputFormat::Text {
+        let report = descriptor.solve(content, &params, &options, &mut |n, part| print_part(day, n, part));
+        if let Some(error) = &report.parse_error {
+            print_parse_error(day, error);
+        }
+        print_parse_alloc(&report);
+        report
+    } else {
+        let report = descriptor.solve(content, &params, &options, &mut |_, _| {});
+        print_results(args.format, &report.results());
+        report
+    };
+
+    if args.record {
+        let mut store = AnswerStore::load(&args.answers)?;
+        store.record(&report);
+        store.save()?;
+    }
+
+    if !report.is_ok() {
+        anyhow::bail!("Day {} did not complete", day);
+    }
+    Ok(())
 }
@@ -0,0 +1,111 @@
+use crate::runner::PartResult;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option(value: &Option<String>) -> String {
+    value.as_deref().map_or("null".to_string(), json_string)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_json(results: &[PartResult]) -> String {
+    let rows: Vec<String> = results
+        .iter()
+        .map(|r| format!(
+            "  {{\"day\": {}, \"part\": \"{}\", \"answer\": {}, \"duration_ns\": {}, \"error\": {}}}",
+            r.day,
+            r.part,
+            json_option(&r.answer),
+            r.duration.as_nanos(),
+            json_option(&r.error),
+        ))
+        .collect();
+
+    if rows.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", rows.join(",\n"))
+    }
+}
+
+pub fn to_csv(results: &[PartResult]) -> String {
+    let mut out = String::from("day,part,answer,duration_ns,error\n");
+    for r in results {
+        out.push_str(&format!(
+            "{},{},{},{},{}\n",
+            r.day,
+            r.part,
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            r.duration.as_nanos(),
+            csv_field(r.error.as_deref().unwrap_or("")),
+        ));
+    }
+    out
+}
+
+/// Prints results in a machine-readable format. Text output is handled by the runner itself.
+pub fn print_results(format: OutputFormat, results: &[PartResult]) {
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", to_json(results)),
+        OutputFormat::Csv => print!("{}", to_csv(results)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::runner::Stage;
+
+    fn result(answer: Option<&str>, error: Option<&str>) -> PartResult {
+        PartResult {
+            day: 3,
+            part: Stage::Part1,
+            answer: answer.map(str::to_string),
+            duration: Duration::from_micros(12),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn json_escapes() {
+        let json = to_json(&[result(None, Some("bad \"line\"\n"))]);
+        assert_eq!(json, "[\n  {\"day\": 3, \"part\": \"part1\", \"answer\": null, \"duration_ns\": 12000, \"error\": \"bad \\\"line\\\"\\n\"}\n]");
+    }
+
+    #[test]
+    fn csv_quotes() {
+        let csv = to_csv(&[result(Some("1,2"), None)]);
+        assert_eq!(csv, "day,part,answer,duration_ns,error\n3,part1,\"1,2\",12000,\n");
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+use std::thread;
+
+/// The number of threads to use for `--jobs`, where 0 means one per core.
+pub fn job_count(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    }
+}
+
+/// Applies `f` to every item on up to `jobs` threads, returning the results in the order of `items`.
+pub fn map_parallel<T, R>(items: Vec<T>, jobs: usize, f: impl Fn(T) -> R + Sync) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+{
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let Some((i, item)) = queue.lock().unwrap().next() else { break };
+                let result = f(item);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results.into_inner().unwrap().into_iter().map(|r| r.expect("every item is processed")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let results = map_parallel((0..20).collect(), 4, |i: u64| {
+            thread::sleep(Duration::from_millis(20 - i));
+            i * 2
+        });
+        assert_eq!(results, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn runs_concurrently() {
+        let start = Instant::now();
+        map_parallel(vec![(); 4], 4, |_| thread::sleep(Duration::from_millis(100)));
+        assert!(start.elapsed() < Duration::from_millis(300));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(map_parallel(Vec::<u8>::new(), 0, |i| i).is_empty());
+    }
+}
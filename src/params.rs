@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use anyhow::{anyhow, bail, Context};
+
+/// A runtime parameter a day accepts, e.g. the size of the field on day 14.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+/// Parameter values passed to a day, set with `--param name=value`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+/// Parses a single `--param name=value` argument.
+pub fn parse_param(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("expected `name=value`, got `{}`", arg)),
+    }
+}
+
+impl Params {
+    pub fn new(values: impl IntoIterator<Item = (String, String)>) -> Self {
+        Params(values.into_iter().collect())
+    }
+
+    /// Returns these params with `name` set to `value`.
+    #[cfg(test)]
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.0.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Only the values for parameters in `declared`.
+    pub fn only(&self, declared: &[Param]) -> Self {
+        Params(
+            self.0
+                .iter()
+                .filter(|(name, _)| declared.iter().any(|p| p.name == *name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Fills in the defaults of `declared` for every value that was not set, and rejects values no day parameter matches.
+    pub fn resolve(&self, day: u8, declared: &[Param]) -> anyhow::Result<Self> {
+        if let Some(unknown) = self.names().find(|name| !declared.iter().any(|p| p.name == *name)) {
+            if declared.is_empty() {
+                bail!("Day {} takes no parameters, got `{}`", day, unknown);
+            }
+            let known: Vec<_> = declared
+                .iter()
+                .map(|p| format!("  {} (default {}): {}", p.name, p.default, p.help))
+                .collect();
+            bail!("Day {} has no parameter `{}`, expected one of:\n{}", day, unknown, known.join("\n"));
+        }
+
+        let mut resolved = self.clone();
+        for param in declared {
+            resolved.0.entry(param.name.to_string()).or_insert_with(|| param.default.to_string());
+        }
+        Ok(resolved)
+    }
+
+    pub fn get_str(&self, name: &str) -> anyhow::Result<&str> {
+        self.0.get(name).map(String::as_str).with_context(|| format!("Missing parameter `{}`", name))
+    }
+
+    pub fn get<T>(&self, name: &str) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.get_str(name)?;
+        value.parse().map_err(|e| anyhow!("Invalid value `{}` for parameter `{}`: {}", value, name, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[Param { name: "blinks", default: "25", help: "" }];
+
+    #[test]
+    fn parse_arg() {
+        assert_eq!(parse_param("field=101x103"), Ok(("field".into(), "101x103".into())));
+        assert!(parse_param("field").is_err());
+        assert!(parse_param("=3").is_err());
+    }
+
+    #[test]
+    fn defaults_and_overrides() {
+        let params = Params::default().resolve(11, DECLARED).unwrap();
+        assert_eq!(params.get::<usize>("blinks").unwrap(), 25);
+
+        let params = Params::default().with("blinks", "40").resolve(11, DECLARED).unwrap();
+        assert_eq!(params.get::<usize>("blinks").unwrap(), 40);
+        assert!(params.get::<usize>("other").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_and_invalid() {
+        assert!(Params::default().with("blinkz", "1").resolve(11, DECLARED).is_err());
+        assert!(Params::default().with("blinks", "1").resolve(1, &[]).is_err());
+        assert!(Params::default().with("blinks", "many").get::<usize>("blinks").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::bail;
+use crate::bench::BenchReport;
+use crate::params::{Param, Params};
+use crate::runner::{Aoc, AocDay, DayReport, PartReport, Parts, RunOptions};
+use crate::years::y2024;
+
+type SolveFn = fn(u16, u8, String, &Params, &RunOptions, &mut dyn FnMut(u8, &PartReport)) -> DayReport;
+type BenchFn = fn(u8, String, &Params, Parts, usize, usize) -> anyhow::Result<BenchReport>;
+
+/// An implemented puzzle: where it belongs in the calendar, what it is called and how to run it.
+pub struct DayDescriptor {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    /// The parts that have a non-interactive solution
+    pub parts: &'static [u8],
+    pub params: &'static [Param],
+    solve_fn: SolveFn,
+    bench_fn: BenchFn,
+}
+
+impl DayDescriptor {
+    pub const fn new<T: AocDay>(year: u16, day: u8, title: &'static str, parts: &'static [u8]) -> Self {
+        DayDescriptor {
+            year,
+            day,
+            title,
+            parts,
+            params: T::PARAMS,
+            solve_fn: <T as Aoc>::solve,
+            bench_fn: <T as Aoc>::bench,
+        }
+    }
+
+    /// The parts to run when `requested` is asked for. `Both` only covers the parts in [`DayDescriptor::parts`],
+    /// while asking for a single part always runs it.
+    pub fn parts_for(&self, requested: Parts) -> Parts {
+        match (requested, self.parts.contains(&1), self.parts.contains(&2)) {
+            (Parts::Both, true, false) => Parts::One,
+            (Parts::Both, false, true) => Parts::Two,
+            (requested, _, _) => requested,
+        }
+    }
+
+    /// Parses `content` and runs the parts in `options`, handing each finished part to `on_part`.
+    /// `params` should already be resolved against [`DayDescriptor::params`].
+    pub fn solve(
+        &self,
+        content: String,
+        params: &Params,
+        options: &RunOptions,
+        on_part: &mut dyn FnMut(u8, &PartReport),
+    ) -> DayReport {
+        (self.solve_fn)(self.year, self.day, content, params, options, on_part)
+    }
+
+    pub fn bench(
+        &self,
+        content: String,
+        params: &Params,
+        parts: Parts,
+        warmup: usize,
+        iterations: usize,
+    ) -> anyhow::Result<BenchReport> {
+        (self.bench_fn)(self.day, content, params, parts, warmup, iterations)
+    }
+}
+
+/// The event this crate started with. Inputs and answers laid out before there were years belong to it.
+pub const LEGACY_YEAR: u16 = 2024;
+
+/// Every implemented day, ordered by year and day.
+pub static DAYS: &[DayDescriptor] = &[
+    DayDescriptor::new::<y2024::dec1::HistorianHysteria>(2024, 1, "Historian Hysteria", &[1, 2]),
+    DayDescriptor::new::<y2024::dec2::RedNosedReports>(2024, 2, "Red-Nosed Reports", &[1, 2]),
+    DayDescriptor::new::<y2024::dec3::MullItOver>(2024, 3, "Mull It Over", &[1, 2]),
+    DayDescriptor::new::<y2024::dec4::CeresSearch>(2024, 4, "Ceres Search", &[1, 2]),
+    DayDescriptor::new::<y2024::dec5::PrintQueue>(2024, 5, "Print Queue", &[1, 2]),
+    DayDescriptor::new::<y2024::dec6::GuardGallivant>(2024, 6, "Guard Gallivant", &[1, 2]),
+    DayDescriptor::new::<y2024::dec7::BridgeRepair>(2024, 7, "Bridge Repair", &[1, 2]),
+    DayDescriptor::new::<y2024::dec8::ResonantCollinearity>(2024, 8, "Resonant Collinearity", &[1, 2]),
+    DayDescriptor::new::<y2024::dec9::DiskFragmenter>(2024, 9, "Disk Fragmenter", &[1, 2]),
+    DayDescriptor::new::<y2024::dec10::HoofIt>(2024, 10, "Hoof It", &[1, 2]),
+    DayDescriptor::new::<y2024::dec11::PlutonianPebbles>(2024, 11, "Plutonian Pebbles", &[1, 2]),
+    DayDescriptor::new::<y2024::dec12::GardenGroups>(2024, 12, "Garden Groups", &[1, 2]),
+    DayDescriptor::new::<y2024::dec13::ClawContraption>(2024, 13, "Claw Contraption", &[1, 2]),
+    // Part 2 is an interactive search for the picture
+    DayDescriptor::new::<y2024::dec14::RestroomRedoubt>(2024, 14, "Restroom Redoubt", &[1]),
+    // Part 2 (the wide warehouse) is not finished yet
+    DayDescriptor::new::<y2024::dec15::WarehouseWoes>(2024, 15, "Warehouse Woes", &[1]),
+    DayDescriptor::new::<y2024::dec16::ReindeerMaze>(2024, 16, "Reindeer Maze", &[1, 2]),
+];
+
+/// The most recent year with any solutions, used when `--year` is not given.
+pub fn latest_year() -> u16 {
+    DAYS.iter().map(|d| d.year).max().unwrap_or(LEGACY_YEAR)
+}
+
+/// The implemented days of `year`.
+pub fn days_of(year: u16) -> impl Iterator<Item = &'static DayDescriptor> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
+
+/// Looks up `day` of `year`, failing with the implemented years or days if it has no solver.
+pub fn find(year: u16, day: u8) -> anyhow::Result<&'static DayDescriptor> {
+    if let Some(descriptor) = days_of(year).find(|d| d.day == day) {
+        return Ok(descriptor);
+    }
+
+    let days: Vec<_> = days_of(year).map(|d| d.day.to_string()).collect();
+    if days.is_empty() {
+        let mut years: Vec<_> = DAYS.iter().map(|d| d.year.to_string()).collect();
+        years.dedup();
+        bail!("There are no solutions for {}, available years are {} (see --list)", year, years.join(", "))
+    }
+    bail!("Day {} of {} is not implemented, available days are {} (see --list)", day, year, days.join(", "))
+}
+
+/// Prints the implemented days, of every year unless `year` is given, as a table for `--list`.
+pub fn print_list(year: Option<u16>) {
+    let title_width = DAYS.iter().map(|d| d.title.len()).max().unwrap_or(0).max("Title".len());
+    println!("{:>4} | {:>3} | {:<title_width$} | {:<5} | Parameters", "Year", "Day", "Title", "Parts");
+    println!("{}", "-".repeat(4 + 3 + 3 + 3 + title_width + 3 + 5 + 3 + "Parameters".len()));
+    for descriptor in DAYS.iter().filter(|d| year.is_none_or(|year| d.year == year)) {
+        let parts: Vec<_> = descriptor.parts.iter().map(u8::to_string).collect();
+        let params: Vec<_> = descriptor.params.iter().map(|p| format!("{}={}", p.name, p.default)).collect();
+        let row = format!(
+            "{:>4} | {:>3} | {:<title_width$} | {:<5} | {}",
+            descriptor.year,
+            descriptor.day,
+            descriptor.title,
+            parts.join(", "),
+            params.join(" "),
+        );
+        println!("{}", row.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert_eq!(find(2024, 16).unwrap().title, "Reindeer Maze");
+        let error = find(2024, 26).err().unwrap().to_string();
+        assert!(error.contains("Day 26 of 2024 is not implemented"), "{}", error);
+        let error = find(1999, 1).err().unwrap().to_string();
+        assert!(error.contains("no solutions for 1999"), "{}", error);
+    }
+
+    #[test]
+    fn both_parts_only_covers_available_ones() {
+        let day = find(2024, 14).unwrap();
+        assert_eq!(day.parts_for(Parts::Both), Parts::One);
+        assert_eq!(day.parts_for(Parts::Two), Parts::Two);
+        assert_eq!(find(2024, 1).unwrap().parts_for(Parts::Both), Parts::Both);
+    }
+}
@@ -0,0 +1,273 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use anyhow::{bail, Context};
+use crate::answers::parse_string;
+use crate::info;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How to reach the puzzle site, read from `~/.config/aoc2024/config.toml`:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// # Optional, for the User-Agent
+/// contact = "me@example.com"
+/// # Optional, e.g. a local stub server
+/// base_url = "http://127.0.0.1:8080"
+/// ```
+///
+/// `AOC_SESSION`, `AOC_CONTACT` and `AOC_BASE_URL` override the file, and `AOC_CONFIG` points to another file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub contact: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    /// Where the config file is looked for.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("aoc2024").join("config.toml"))
+    }
+
+    /// Reads the config file, if there is one, and applies the environment on top.
+    pub fn load() -> anyhow::Result<Self> {
+        let mut config = match Self::path() {
+            Some(path) if path.exists() => {
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Could not read config from {}", path.display()))?;
+                Self::parse(&content).with_context(|| format!("Invalid config file {}", path.display()))?
+            }
+            _ => Config::default(),
+        };
+
+        for (var, value) in [
+            ("AOC_SESSION", &mut config.session),
+            ("AOC_CONTACT", &mut config.contact),
+            ("AOC_BASE_URL", &mut config.base_url),
+        ] {
+            if let Ok(env) = std::env::var(var) {
+                *value = Some(env);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut config = Config::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let context = || format!("line {}: `{}`", i + 1, line);
+            let (key, value) = line.split_once('=').with_context(|| format!("Expected `key = \"value\"` on {}", context()))?;
+            let value = Some(parse_string(value.trim()).with_context(context)?);
+            match key.trim() {
+                "session" => config.session = value,
+                "contact" => config.contact = value,
+                "base_url" => config.base_url = value,
+                other => bail!("Unknown key `{}` on {}", other, context()),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Talks to the puzzle site through `curl`, which brings the TLS support std lacks.
+pub struct Client {
+    base_url: String,
+    session: String,
+    user_agent: String,
+}
+
+/// The status code and body of a response.
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let Some(session) = config.session.clone() else {
+            let path = Config::path().map_or("the config file".to_string(), |p| p.display().to_string());
+            bail!("No session token, set AOC_SESSION or `session = \"...\"` in {}", path);
+        };
+
+        let mut user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        if let Some(contact) = &config.contact {
+            user_agent += &format!(" (contact: {})", contact);
+        }
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL).trim_end_matches('/').to_string();
+        Ok(Client { base_url, session, user_agent })
+    }
+
+    /// Sends a request for `path` below the base URL, as a form POST if `form` is given.
+    pub fn request(&self, path: &str, form: Option<&str>) -> anyhow::Result<Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--user-agent", &self.user_agent])
+            // The cookie is passed on stdin so the token does not show up in the process list
+            .args(["--header", "@-", "--write-out", "\n%{http_code}"]);
+        if let Some(form) = form {
+            command.args(["--data", form]);
+        }
+        let mut child = command
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Could not run curl, is it installed?")?;
+        writeln!(child.stdin.take().unwrap(), "Cookie: session={}", self.session)?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("Request to {} failed: {}", url, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        let output = String::from_utf8(output.stdout).with_context(|| format!("{} sent invalid UTF-8", url))?;
+        let (body, status) = output.rsplit_once('\n').with_context(|| format!("No status from {}", url))?;
+        let status = status.parse().with_context(|| format!("Invalid status `{}` from {}", status, url))?;
+        Ok(Response { status, body: body.to_string() })
+    }
+}
+
+/// Downloads the input of `day` to `path`, unless it is already there. Returns whether it downloaded.
+pub fn fetch_input(client: &Client, year: u16, day: u8, path: &Path) -> anyhow::Result<bool> {
+    if path.exists() {
+        info!("[-] Day {} input is already at {}", day, path.display());
+        return Ok(false);
+    }
+
+    let response = client.request(&format!("/{}/day/{}/input", year, day), None)?;
+    match response.status {
+        200 => {}
+        400 => bail!("The session token was rejected, it may have expired"),
+        404 => bail!("The input of day {} of {} is not available (yet)", day, year),
+        status => bail!("Downloading the input failed with HTTP {}: {}", status, response.body.lines().next().unwrap_or("")),
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+    std::fs::write(path, response.body).with_context(|| format!("Could not write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{channel, Receiver};
+
+    /// A request as the stub server saw it.
+    pub struct Request {
+        pub line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    /// Serves `responses` as `(status, body)` to one request each on a local port, returning its base URL
+    /// and the requests it received.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut headers = Vec::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(header.trim().to_string());
+                }
+                let length = headers
+                    .iter()
+                    .find_map(|h| h.to_ascii_lowercase().strip_prefix("content-length:").map(|l| l.trim().parse().unwrap()))
+                    .unwrap_or(0);
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(stream, "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body)
+                    .unwrap();
+                let body = String::from_utf8(request_body).unwrap();
+                sender.send(Request { line: line.trim().to_string(), headers, body }).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(base_url: &str) -> Client {
+        let config = Config {
+            session: Some("secret".into()),
+            contact: Some("me@example.com".into()),
+            base_url: Some(format!("{}/", base_url)),
+        };
+        Client::new(&config).unwrap()
+    }
+
+    #[test]
+    fn config_file() {
+        let config = Config::parse("# mine\nsession = \"abc\"\nbase_url = \"http://localhost\"\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost"));
+        assert_eq!(config.contact, None);
+        assert!(Config::parse("token = \"abc\"").is_err());
+        assert!(Client::new(&Config::default()).is_err());
+    }
+
+    #[test]
+    fn fetches_once() {
+        let (url, requests) = stub::serve(vec![(200, "1 2\n3 4\n")]);
+        let path = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id())).join("1.txt");
+        let client = client(&url);
+
+        assert!(fetch_input(&client, 2024, 1, &path).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "GET /2024/day/1/input HTTP/1.1");
+        assert!(request.body.is_empty());
+        assert!(request.headers.contains(&"Cookie: session=secret".to_string()));
+        let agent = request.headers.iter().find(|h| h.starts_with("User-Agent:")).unwrap();
+        assert!(agent.contains("aoc2024/") && agent.contains("me@example.com"), "{}", agent);
+
+        // The stub only answers once, so this must not hit the network
+        assert!(!fetch_input(&client, 2024, 1, &path).unwrap());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let (url, _requests) = stub::serve(vec![(400, "Please log in"), (404, "Not found")]);
+        let path = std::env::temp_dir().join(format!("aoc-fetch-errors-{}", std::process::id())).join("2.txt");
+        let client = client(&url);
+
+        let error = fetch_input(&client, 2024, 2, &path).unwrap_err().to_string();
+        assert!(error.contains("session token was rejected"), "{}", error);
+        let error = fetch_input(&client, 2024, 2, &path).unwrap_err().to_string();
+        assert!(error.contains("not available"), "{}", error);
+        assert!(!path.exists());
+    }
+}
@@ -1,45 +1,378 @@
-pub trait AocDay {
-    fn new(content: String) -> Self;
-    fn part1(&self) -> String;
-    fn part2(&self) -> String;
+use std::fmt::{Display, Formatter};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, Context};
+use crate::alloc::{track, AllocStats};
+use crate::bench::{measure, BenchReport};
+use crate::cancel::{CancelToken, Timeout};
+use crate::capture;
+use crate::params::{Param, Params};
+
+pub trait AocDay: Sized + Send + Sync + 'static {
+    /// Runtime parameters this day reads from `params`, with their defaults.
+    const PARAMS: &'static [Param] = &[];
+
+    fn new(content: String, params: &Params) -> anyhow::Result<Self>;
+    fn part1(&self) -> anyhow::Result<String>;
+    fn part2(&self) -> anyhow::Result<String>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(&self, part: u8) -> bool {
+        match self {
+            Parts::One => part == 1,
+            Parts::Two => part == 2,
+            Parts::Both => true,
+        }
+    }
+}
+
+/// How the parts of a day are run.
+#[derive(Clone, Copy, Debug)]
+pub struct RunOptions {
+    pub parts: Parts,
+    /// Give up on a part after this long
+    pub timeout: Option<Duration>,
+    /// Run part 1 and part 2 at the same time
+    pub parallel: bool,
+}
+
+impl RunOptions {
+    pub fn new(parts: Parts) -> Self {
+        RunOptions { parts, timeout: None, parallel: false }
+    }
+}
+
+pub struct PartReport {
+    pub answer: anyhow::Result<String>,
+    pub time: Duration,
+    /// Only counted with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>,
+}
+
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub parse_error: Option<anyhow::Error>,
+    pub part1: Option<PartReport>,
+    pub part2: Option<PartReport>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part1"),
+            Stage::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+/// One row of machine-readable output: the outcome of a single stage of a day.
+pub struct PartResult {
+    pub day: u8,
+    pub part: Stage,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    fn parse_failed(year: u16, day: u8, parse_time: Duration, error: anyhow::Error) -> Self {
+        DayReport { year, day, parse_time, parse_alloc: None, parse_error: Some(error), part1: None, part2: None }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.parse_error.is_none()
+            && [&self.part1, &self.part2].iter().all(|p| p.as_ref().is_none_or(|p| p.answer.is_ok()))
+    }
+
+    pub fn results(&self) -> Vec<PartResult> {
+        let mut results = vec![PartResult {
+            day: self.day,
+            part: Stage::Parse,
+            answer: None,
+            duration: self.parse_time,
+            error: self.parse_error.as_ref().map(|e| format!("{:#}", e)),
+        }];
+
+        for (stage, part) in [(Stage::Part1, &self.part1), (Stage::Part2, &self.part2)] {
+            if let Some(part) = part {
+                results.push(PartResult {
+                    day: self.day,
+                    part: stage,
+                    answer: part.answer.as_ref().ok().cloned(),
+                    duration: part.time,
+                    error: part.answer.as_ref().err().map(|e| format!("{:#}", e)),
+                });
+            }
+        }
+
+        results
+    }
 }
 
 pub trait Aoc {
-    fn run(content: String);
-    #[cfg(test)]
-    fn test_1(content: String, result: String);
-    #[cfg(test)]
-    fn test_2(content: String, result: String);
+    /// Parses `content` and runs the parts in `options`, handing each finished part to `on_part`.
+    fn solve(year: u16, day: u8, content: String, params: &Params, options: &RunOptions, on_part: &mut dyn FnMut(u8, &PartReport)) -> DayReport;
+    fn bench(day: u8, content: String, params: &Params, parts: Parts, warmup: usize, iterations: usize) -> anyhow::Result<BenchReport>;
+}
+
+pub static ANSI_GREEN_PLUS: &str = "[\x1b[32m+\x1b[0m]";
+pub static ANSI_RED_BANG: &str = "[\x1b[31m!\x1b[0m]";
+
+/// Runs `f`, measuring how long it takes and, with the `alloc-stats` feature, what it allocates.
+fn timed<R>(f: impl FnOnce() -> R) -> (R, Duration, Option<AllocStats>) {
+    let start = Instant::now();
+    let (result, alloc) = track(f);
+    (result, start.elapsed(), alloc)
+}
+
+/// Runs `part` of `solver`. With a timeout it runs on a worker thread, which is cancelled and abandoned
+/// if it does not answer in time.
+fn run_part<T: AocDay>(
+    enabled: bool,
+    timeout: Option<Duration>,
+    solver: &Arc<T>,
+    part: fn(&T) -> anyhow::Result<String>,
+) -> Option<PartReport> {
+    if !enabled {
+        return None;
+    }
+
+    let Some(timeout) = timeout else {
+        let (answer, time, alloc) = timed(|| part(solver));
+        return Some(PartReport { answer, time, alloc });
+    };
+
+    let token = CancelToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let worker = {
+        let solver = Arc::clone(solver);
+        let token = token.clone();
+        let task = capture::inherit(move || timed(|| part(&solver)));
+        thread::spawn(move || {
+            CancelToken::set_current(token);
+            // The runner stops listening after a timeout, so there may be no one to send to
+            let _ = sender.send(task());
+        })
+    };
+
+    let start = Instant::now();
+    let report = match receiver.recv_timeout(timeout) {
+        Ok(((answer, time, alloc), output)) => {
+            capture::forward(output);
+            PartReport { answer, time, alloc }
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            PartReport { answer: Err(Timeout(timeout).into()), time: start.elapsed(), alloc: None }
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let payload = worker.join().err();
+            let message = payload
+                .as_ref()
+                .and_then(|p| p.downcast_ref::<&str>().copied().or(p.downcast_ref::<String>().map(String::as_str)))
+                .unwrap_or("unknown reason");
+            PartReport { answer: Err(anyhow!("panicked: {}", message)), time: start.elapsed(), alloc: None }
+        }
+    };
+    Some(report)
+}
+
+/// Runs both parts, side by side if `options.parallel` is set. Their output is kept in part order.
+fn run_parts<T: AocDay>(
+    solver: &Arc<T>,
+    options: &RunOptions,
+    on_part: &mut dyn FnMut(u8, &PartReport),
+) -> (Option<PartReport>, Option<PartReport>) {
+    let part1 = || run_part(options.parts.includes(1), options.timeout, solver, T::part1);
+    let part2 = || run_part(options.parts.includes(2), options.timeout, solver, T::part2);
+    let mut report = |n, part: Option<PartReport>| {
+        if let Some(part) = &part {
+            on_part(n, part);
+        }
+        part
+    };
+
+    if !options.parallel {
+        let part1 = report(1, part1());
+        return (part1, report(2, part2()));
+    }
+
+    thread::scope(|scope| {
+        let worker = scope.spawn(capture::inherit(part1));
+        let (part2, output2) = capture::inherit(part2)();
+        let (part1, output1) = worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        capture::forward(output1);
+        capture::forward(output2);
+        (report(1, part1), report(2, part2))
+    })
 }
 
-static ANSI_GREEN_PLUS: &str = "[\x1b[32m+\x1b[0m]";
+pub fn print_part(day: u8, n: u8, report: &PartReport) {
+    match &report.answer {
+        Ok(answer) => match &report.alloc {
+            Some(alloc) => println!("{} Part {}: {} ({:.2?}, {})", ANSI_GREEN_PLUS, n, answer, report.time, alloc),
+            None => println!("{} Part {}: {} ({:.2?})", ANSI_GREEN_PLUS, n, answer, report.time),
+        },
+        Err(error) => println!("{} Day {} part {} failed: {:#}", ANSI_RED_BANG, day, n, error),
+    }
+}
+
+/// Prints what parsing allocated, if the `alloc-stats` feature counted it.
+pub fn print_parse_alloc(report: &DayReport) {
+    if let (Some(alloc), None) = (&report.parse_alloc, &report.parse_error) {
+        println!("[-] Parsing: {:.2?}, {}", report.parse_time, alloc);
+    }
+}
+
+pub fn print_parse_error(day: u8, error: &anyhow::Error) {
+    println!("{} Day {} could not parse its input: {:#}", ANSI_RED_BANG, day, error);
+}
 
 impl <T> Aoc for T where T: AocDay {
-    fn run(content: String) {
-        let day = T::new(content);
-        let part1start = std::time::Instant::now();
-        let part1 = day.part1();
-        let part1end = std::time::Instant::now();
-        println!("{} Part 1: {} ({}ms)", ANSI_GREEN_PLUS, part1, part1end.duration_since(part1start).as_millis());
+    fn solve(year: u16, day: u8, content: String, params: &Params, options: &RunOptions, on_part: &mut dyn FnMut(u8, &PartReport)) -> DayReport {
+        let (solver, parse_time, parse_alloc) = timed(|| T::new(content, params));
+        let solver = match solver {
+            Ok(solver) => Arc::new(solver),
+            Err(error) => return DayReport::parse_failed(year, day, parse_time, error),
+        };
+        let (part1, part2) = run_parts(&solver, options, on_part);
+
+        DayReport { year, day, parse_time, parse_alloc, parse_error: None, part1, part2 }
+    }
+
+    fn bench(day: u8, content: String, params: &Params, parts: Parts, warmup: usize, iterations: usize) -> anyhow::Result<BenchReport> {
+        // Run everything once up front so a failing day is reported instead of benchmarked
+        let solver = T::new(content.clone(), params).with_context(|| format!("Day {} could not parse its input", day))?;
+        if parts.includes(1) {
+            solver.part1().with_context(|| format!("Day {} part 1 failed", day))?;
+        }
+        if parts.includes(2) {
+            solver.part2().with_context(|| format!("Day {} part 2 failed", day))?;
+        }
+
+        let parse = measure(warmup, iterations, || content.clone(), |content| T::new(content, params));
+        let part1 = parts.includes(1).then(|| measure(warmup, iterations, || (), |_| solver.part1()));
+        let part2 = parts.includes(2).then(|| measure(warmup, iterations, || (), |_| solver.part2()));
 
-        let part2start = std::time::Instant::now();
-        let part2 = day.part2();
-        let part2end = std::time::Instant::now();
+        Ok(BenchReport { day, warmup, iterations, parse, part1, part2 })
+    }
+}
 
-        println!("{} Part 2: {} ({}ms)", ANSI_GREEN_PLUS, part2, part2end.duration_since(part2start).as_millis());
+fn answer_cell(report: &Option<PartReport>) -> &str {
+    match report {
+        Some(PartReport { answer: Ok(answer), .. }) => answer.as_str(),
+        Some(PartReport { answer: Err(error), .. }) if error.is::<Timeout>() => "timeout",
+        Some(PartReport { answer: Err(_), .. }) => "error",
+        None => "-",
     }
+}
+
+fn time_cell(report: &Option<PartReport>) -> String {
+    report.as_ref().map_or("-".to_string(), |r| format!("{:.2?}", r.time))
+}
 
-    #[cfg(test)]
-    fn test_1(content: String, result: String) {
-        let day = T::new(content);
-        let part1 = day.part1();
-        assert_eq!(part1, result, "Expected: {}, Got: {}", result, part1);
+fn part_time(report: &Option<PartReport>) -> Duration {
+    report.as_ref().map_or(Duration::ZERO, |r| r.time)
+}
+
+fn alloc_cell(alloc: Option<AllocStats>) -> String {
+    alloc.map_or("-".to_string(), |a| a.to_string())
+}
+
+/// Prints what each stage allocated, if the `alloc-stats` feature counted anything.
+fn print_alloc_summary(reports: &[DayReport]) {
+    if reports.iter().all(|r| r.parse_alloc.is_none()) {
+        return;
     }
 
-    #[cfg(test)]
-    fn test_2(content: String, result: String) {
-        let day = T::new(content);
-        let part2 = day.part2();
-        assert_eq!(part2, result, "Expected: {}, Got: {}", result, part2);
+    let rows: Vec<_> = reports
+        .iter()
+        .map(|r| {
+            let part = |p: &Option<PartReport>| alloc_cell(p.as_ref().and_then(|p| p.alloc));
+            (r.day, alloc_cell(r.parse_alloc), part(&r.part1), part(&r.part2))
+        })
+        .collect();
+    let width = |f: fn(&(u8, String, String, String)) -> &String, title: &str| {
+        rows.iter().map(|r| f(r).len()).chain([title.len()]).max().unwrap_or(0)
+    };
+    let w0 = width(|r| &r.1, "Parse");
+    let w1 = width(|r| &r.2, "Part 1");
+    let w2 = width(|r| &r.3, "Part 2");
+
+    println!();
+    println!("Day | {:<w0$} | {:<w1$} | Part 2", "Parse", "Part 1");
+    println!("{}", "-".repeat(3 + 3 + w0 + 3 + w1 + 3 + w2));
+    for (day, parse, part1, part2) in &rows {
+        println!("{:>3} | {:<w0$} | {:<w1$} | {}", day, parse, part1, part2);
+    }
+}
+
+pub fn print_summary(reports: &[DayReport]) {
+    let width = |f: fn(&DayReport) -> &str, title: &str| {
+        reports.iter().map(|r| f(r).len()).chain([title.len()]).max().unwrap_or(0)
+    };
+    let w1 = width(|r| answer_cell(&r.part1), "Part 1");
+    let w2 = width(|r| answer_cell(&r.part2), "Part 2");
+
+    println!(
+        "{:>3} | {:<w1$} | {:<w2$} | {:>10} | {:>10} | {:>10}",
+        "Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2",
+    );
+    println!("{}", "-".repeat(3 + 3 + w1 + 3 + w2 + 3 * (3 + 10)));
+
+    let mut total = Duration::ZERO;
+    for report in reports {
+        if report.parse_error.is_some() {
+            println!("{:>3} | {:<w1$} | {:<w2$} | {:>10} | {:>10} | {:>10}", report.day, "error", "error", "-", "-", "-");
+            continue;
+        }
+
+        println!(
+            "{:>3} | {:<w1$} | {:<w2$} | {:>10} | {:>10} | {:>10}",
+            report.day,
+            answer_cell(&report.part1),
+            answer_cell(&report.part2),
+            format!("{:.2?}", report.parse_time),
+            time_cell(&report.part1),
+            time_cell(&report.part2),
+        );
+        total += report.parse_time + part_time(&report.part1) + part_time(&report.part2);
+    }
+
+    println!("{} {} days in {:.2?}", ANSI_GREEN_PLUS, reports.len(), total);
+    print_alloc_summary(reports);
+
+    for report in reports {
+        if let Some(error) = &report.parse_error {
+            print_parse_error(report.day, error);
+        }
+        for (n, part) in [(1, &report.part1), (2, &report.part2)] {
+            if let Some(part @ PartReport { answer: Err(_), .. }) = part {
+                print_part(report.day, n, part);
+            }
+        }
     }
 }
@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context};
+
+const DAY_TEMPLATE: &str = r#"use anyhow::bail;
+use crate::params::Params;
+use crate::runner::AocDay;
+
+pub struct {name} {
+    lines: Vec<String>,
+}
+
+impl AocDay for {name} {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        let lines = content.lines().map(str::to_string).collect();
+        Ok({name} { lines })
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        bail!("Part 1 is not solved yet ({} lines of input)", self.lines.len())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        bail!("Part 2 is not solved yet ({} lines of input)", self.lines.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let content = include_str!("../../../inputs/{year}/{day}_test1.txt").to_string();
+        assert!({name}::new(content, &Params::default()).is_ok());
+    }
+}
+"#;
+
+const EXPECTED_TEMPLATE: &str = "# The answers for {day}_test1.txt, the example is skipped until one is filled in\n\
+                                 # part1 = \"\"\n\
+                                 # part2 = \"\"\n";
+
+/// A day for `new-day` to scaffold: the solver module, its registry entry and an example input.
+pub struct NewDay {
+    pub year: u16,
+    pub day: u8,
+    /// The solver type, e.g. `ChronospatialComputer`
+    pub name: String,
+    pub title: String,
+}
+
+/// Splits a type name into words, `ChronospatialComputer` becomes `Chronospatial Computer`.
+fn title_from_name(name: &str) -> String {
+    let mut title = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            title.push(' ');
+        }
+        title.push(c);
+    }
+    title
+}
+
+/// Inserts `line` after the last line with a smaller key, or before the first keyed line if there is none.
+/// Returns `None` if no line has a key.
+fn insert_sorted(content: &str, line: &str, key: impl Fn(&str) -> Option<u32>) -> Option<String> {
+    let new_key = key(line)?;
+    let lines: Vec<_> = content.lines().collect();
+    let keyed: Vec<_> = lines.iter().enumerate().filter_map(|(i, l)| Some((i, key(l)?))).collect();
+    let at = match keyed.iter().rev().find(|(_, k)| *k < new_key) {
+        Some((i, _)) => i + 1,
+        None => keyed.first()?.0,
+    };
+
+    let mut out: Vec<_> = lines[..at].to_vec();
+    out.push(line);
+    out.extend(&lines[at..]);
+    Some(out.join("\n") + "\n")
+}
+
+/// The number in a `pub mod <prefix><number>;` line.
+fn mod_key(prefix: &'static str) -> impl Fn(&str) -> Option<u32> {
+    move |line| line.trim().strip_prefix("pub mod ")?.strip_prefix(prefix)?.strip_suffix(';')?.parse().ok()
+}
+
+/// The year and day of a `DayDescriptor::new::<...>(2024, 1, ...)` registry line, as one sortable number.
+fn entry_key(line: &str) -> Option<u32> {
+    let (_, args) = line.trim().strip_prefix("DayDescriptor::new::<")?.split_once(">(")?;
+    let mut args = args.split(',').map(|arg| arg.trim().parse::<u32>());
+    Some(args.next()?.ok()? * 100 + args.next()?.ok()?)
+}
+
+/// Adds `pub mod <prefix><number>;` to a module list, creating it if `content` is empty.
+fn add_mod(content: &str, prefix: &'static str, number: u32) -> String {
+    let line = format!("pub mod {}{};", prefix, number);
+    insert_sorted(content, &line, mod_key(prefix)).unwrap_or_else(|| format!("{}{}\n", content, line))
+}
+
+impl NewDay {
+    pub fn new(year: u16, day: u8, name: &str, title: Option<&str>) -> anyhow::Result<Self> {
+        if !(1..=25).contains(&day) {
+            bail!("Day {} is not in the calendar, days go from 1 to 25", day);
+        }
+        let valid_name = name.starts_with(|c: char| c.is_ascii_uppercase()) && name.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_name {
+            bail!("`{}` is not a type name, use something like `ChronospatialComputer`", name);
+        }
+
+        let title = title.map_or_else(|| title_from_name(name), str::to_string);
+        Ok(NewDay { year, day, name: name.to_string(), title })
+    }
+
+    /// Creates the day below the crate directory `root` and returns the files it wrote.
+    /// Nothing is written if any part of the day already exists.
+    pub fn create(&self, root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(root.join(path)).with_context(|| format!("Could not read {}", path.display()))
+        };
+        let read_or_empty = |path: &Path| if root.join(path).exists() { read(path) } else { Ok(String::new()) };
+
+        let day_path = PathBuf::from(format!("src/years/y{}/dec{}.rs", self.year, self.day));
+        let year_path = PathBuf::from(format!("src/years/y{}.rs", self.year));
+        let years_path = PathBuf::from("src/years.rs");
+        let registry_path = PathBuf::from("src/registry.rs");
+        let example_path = PathBuf::from(format!("inputs/{}/{}_test1.txt", self.year, self.day));
+        let expected_path = example_path.with_extension("expected");
+
+        for path in [&day_path, &example_path, &expected_path] {
+            if root.join(path).exists() {
+                bail!("{} already exists, not touching day {} of {}", path.display(), self.day, self.year);
+            }
+        }
+
+        let registry = read(&registry_path)?;
+        let key = self.year as u32 * 100 + self.day as u32;
+        if registry.lines().any(|line| entry_key(line) == Some(key)) {
+            bail!("Day {} of {} is already in {}", self.day, self.year, registry_path.display());
+        }
+        let entry = format!(
+            "    DayDescriptor::new::<y{}::dec{}::{}>({}, {}, {:?}, &[1, 2]),",
+            self.year, self.day, self.name, self.year, self.day, self.title
+        );
+        let mut registry = insert_sorted(&registry, &entry, entry_key)
+            .with_context(|| format!("Could not find the list of days in {}", registry_path.display()))?;
+        let import = format!("use crate::years::y{};", self.year);
+        if !registry.lines().any(|line| line == import) {
+            let year_key = |line: &str| line.strip_prefix("use crate::years::y")?.strip_suffix(';')?.parse().ok();
+            registry = insert_sorted(&registry, &import, year_key)
+                .with_context(|| format!("Could not find the imports of the years in {}", registry_path.display()))?;
+        }
+
+        let year_mod = read_or_empty(&year_path)?;
+        if year_mod.lines().any(|line| mod_key("dec")(line) == Some(self.day as u32)) {
+            bail!("dec{} is already declared in {}", self.day, year_path.display());
+        }
+
+        let day_file = DAY_TEMPLATE
+            .replace("{name}", &self.name)
+            .replace("{year}", &self.year.to_string())
+            .replace("{day}", &self.day.to_string());
+        let mut files = vec![
+            (day_path, day_file),
+            (year_path, add_mod(&year_mod, "dec", self.day as u32)),
+            (registry_path, registry),
+            (example_path, String::new()),
+            (expected_path, EXPECTED_TEMPLATE.replace("{day}", &self.day.to_string())),
+        ];
+        let years = read(&years_path)?;
+        if !years.lines().any(|line| mod_key("y")(line) == Some(self.year as u32)) {
+            files.push((years_path, add_mod(&years, "y", self.year as u32)));
+        }
+
+        for (path, content) in &files {
+            let full = root.join(path);
+            if let Some(dir) = full.parent() {
+                std::fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+            }
+            std::fs::write(&full, content).with_context(|| format!("Could not write {}", path.display()))?;
+        }
+        Ok(files.into_iter().map(|(path, _)| path).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "use crate::years::y2024;\n\npub static DAYS: &[DayDescriptor] = &[\n    \
+        DayDescriptor::new::<y2024::dec1::HistorianHysteria>(2024, 1, \"Historian Hysteria\", &[1, 2]),\n    \
+        // Only part 1\n    \
+        DayDescriptor::new::<y2024::dec16::ReindeerMaze>(2024, 16, \"Reindeer Maze\", &[1]),\n];\n";
+
+    fn crate_dir(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/years")).unwrap();
+        std::fs::write(root.join("src/registry.rs"), REGISTRY).unwrap();
+        std::fs::write(root.join("src/years.rs"), "pub mod y2024;\n").unwrap();
+        std::fs::write(root.join("src/years/y2024.rs"), "pub mod dec1;\npub mod dec16;\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(NewDay::new(2024, 17, "ChronospatialComputer", None).unwrap().title, "Chronospatial Computer");
+        assert_eq!(NewDay::new(2024, 17, "Computer", Some("Chrono")).unwrap().title, "Chrono");
+        assert!(NewDay::new(2024, 26, "Late", None).is_err());
+        assert!(NewDay::new(2024, 17, "not_a_type", None).is_err());
+    }
+
+    #[test]
+    fn creates_day() {
+        let root = crate_dir("creates");
+        let written = NewDay::new(2024, 5, "PrintQueue", None).unwrap().create(&root).unwrap();
+        assert_eq!(written.len(), 5);
+
+        let registry = std::fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        let entries: Vec<_> = registry.lines().filter_map(entry_key).collect();
+        assert_eq!(entries, vec![202401, 202405, 202416]);
+        assert!(registry.contains("// Only part 1\n    DayDescriptor::new::<y2024::dec16"));
+        assert!(registry.contains("<y2024::dec5::PrintQueue>(2024, 5, \"Print Queue\", &[1, 2])"));
+
+        let year = std::fs::read_to_string(root.join("src/years/y2024.rs")).unwrap();
+        assert_eq!(year, "pub mod dec1;\npub mod dec5;\npub mod dec16;\n");
+        let day = std::fs::read_to_string(root.join("src/years/y2024/dec5.rs")).unwrap();
+        assert!(day.contains("impl AocDay for PrintQueue"));
+        assert!(day.contains("inputs/2024/5_test1.txt"));
+        assert!(root.join("inputs/2024/5_test1.expected").exists());
+
+        let again = NewDay::new(2024, 5, "Other", None).unwrap().create(&root);
+        assert!(again.unwrap_err().to_string().contains("already exists"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn creates_year() {
+        let root = crate_dir("year");
+        NewDay::new(2023, 1, "Trebuchet", None).unwrap().create(&root).unwrap();
+
+        let registry = std::fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        assert!(registry.starts_with("use crate::years::y2023;\nuse crate::years::y2024;\n"));
+        assert_eq!(registry.lines().filter_map(entry_key).next(), Some(202301));
+        assert_eq!(std::fs::read_to_string(root.join("src/years.rs")).unwrap(), "pub mod y2023;\npub mod y2024;\n");
+        assert_eq!(std::fs::read_to_string(root.join("src/years/y2023.rs")).unwrap(), "pub mod dec1;\n");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn refuses_registered_day() {
+        let root = crate_dir("registered");
+        let error = NewDay::new(2024, 16, "ReindeerMaze", None).unwrap().create(&root).unwrap_err();
+        assert!(error.to_string().contains("already in"), "{}", error);
+        assert!(!root.join("src/years/y2024/dec16.rs").exists());
+        assert_eq!(std::fs::read_to_string(root.join("src/registry.rs")).unwrap(), REGISTRY);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
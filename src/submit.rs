@@ -0,0 +1,355 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{bail, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::answers::{parse_string, quote};
+use crate::remote::Client;
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+}
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Wrong,
+    /// Not checked because the last submission was too recent
+    RateLimited(Duration),
+    /// The part was solved before, so the answer was not checked
+    AlreadySolved,
+    /// A response this code does not understand
+    Unknown,
+}
+
+impl Outcome {
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate-limited:{}", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "already-solved"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl std::str::FromStr for Outcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "already-solved" => Outcome::AlreadySolved,
+            "unknown" => Outcome::Unknown,
+            s => match s.strip_prefix("rate-limited:") {
+                Some(secs) => Outcome::RateLimited(Duration::from_secs(secs.parse()?)),
+                None => bail!("Unknown outcome `{}`", s),
+            },
+        })
+    }
+}
+
+/// The message in a response to a submission, without the HTML around it.
+pub fn response_message(body: &str) -> String {
+    let article = ARTICLE.captures(body).map_or(body, |c| c.get(1).unwrap().as_str());
+    let text = TAG.replace_all(article, "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the outcome from the message of a response, see [`response_message`].
+pub fn parse_outcome(message: &str) -> Outcome {
+    if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if message.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if let Some(wait) = WAIT.captures(message) {
+        let minutes: u64 = wait.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = wait[2].parse().unwrap();
+        Outcome::RateLimited(Duration::from_secs(minutes * 60 + seconds))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown
+    }
+}
+
+/// One submission, as kept in the history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    /// Seconds since the epoch
+    pub time: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Every answer submitted so far, one per line, so wrong answers and learned bounds are not tried again:
+///
+/// ```text
+/// 1733900000 2024 11 1 too-low "1234"
+/// ```
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+impl History {
+    /// Loads the history at `path`. A missing file is an empty history.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let mut history = History { path: path.to_path_buf(), attempts: Vec::new() };
+        if !path.exists() {
+            return Ok(history);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read submissions from {}", path.display()))?;
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let attempt = Self::parse_attempt(line)
+                .with_context(|| format!("Invalid submission on line {} of {}: `{}`", i + 1, path.display(), line))?;
+            history.attempts.push(attempt);
+        }
+        Ok(history)
+    }
+
+    fn parse_attempt(line: &str) -> anyhow::Result<Attempt> {
+        let mut fields = line.splitn(6, ' ');
+        let mut next = || fields.next().context("Expected `time year day part outcome \"answer\"`");
+        Ok(Attempt {
+            time: next()?.parse()?,
+            year: next()?.parse()?,
+            day: next()?.parse()?,
+            part: next()?.parse()?,
+            outcome: next()?.parse()?,
+            answer: parse_string(next()?)?,
+        })
+    }
+
+    pub fn attempts(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| (a.year, a.day, a.part) == (year, day, part))
+    }
+
+    /// Fails if submitting `answer` is pointless: the part is solved, the answer was wrong before or lies outside
+    /// the bounds learned from answers that were too high or too low, or the site asked to wait until after `now`.
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> anyhow::Result<()> {
+        if let Some(Attempt { time, outcome: Outcome::RateLimited(wait), .. }) = self.attempts.last() {
+            let until = time + wait.as_secs();
+            if until > now {
+                bail!("The site asked to wait, try again in {}s", until - now);
+            }
+        }
+
+        let value: Option<i128> = answer.parse().ok();
+        for attempt in self.attempts(year, day, part) {
+            let known: Option<i128> = attempt.answer.parse().ok();
+            match (&attempt.outcome, value, known) {
+                (Outcome::Correct, _, _) | (Outcome::AlreadySolved, _, _) => {
+                    bail!("Day {} part {} is already solved", day, part)
+                }
+                (outcome, _, _) if outcome.is_wrong() && attempt.answer == answer => {
+                    bail!("{} was already submitted and is wrong ({})", answer, outcome)
+                }
+                (Outcome::TooHigh, Some(value), Some(known)) if value >= known => {
+                    bail!("{} is not below {}, which was too high", answer, known)
+                }
+                (Outcome::TooLow, Some(value), Some(known)) if value <= known => {
+                    bail!("{} is not above {}, which was too low", answer, known)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut out = String::from("# time year day part outcome answer\n");
+        for a in &self.attempts {
+            out += &format!("{} {} {} {} {} {}\n", a.time, a.year, a.day, a.part, a.outcome, quote(&a.answer));
+        }
+        std::fs::write(&self.path, out).with_context(|| format!("Could not write submissions to {}", self.path.display()))
+    }
+}
+
+/// Percent-encodes `value` for a form body.
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Submits `answer` for `part` unless the history says it is pointless, and records the attempt.
+/// Returns the outcome and the message of the site.
+pub fn submit_answer(
+    client: &Client,
+    history: &mut History,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> anyhow::Result<(Outcome, String)> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        bail!("Refusing to submit an empty answer");
+    }
+    history.check(year, day, part, answer, now())?;
+
+    let form = format!("level={}&answer={}", part, form_encode(answer));
+    let response = client.request(&format!("/{}/day/{}/answer", year, day), Some(&form))?;
+    if response.status != 200 {
+        bail!("Submitting failed with HTTP {}: {}", response.status, response_message(&response.body));
+    }
+
+    let message = response_message(&response.body);
+    let outcome = parse_outcome(&message);
+    history.record(Attempt { time: now(), year, day, part, answer: answer.to_string(), outcome: outcome.clone() });
+    history.save()?;
+    Ok((outcome, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::{stub, Config};
+
+    fn attempt(time: u64, answer: &str, outcome: Outcome) -> Attempt {
+        Attempt { time, year: 2024, day: 1, part: 1, answer: answer.to_string(), outcome }
+    }
+
+    fn history(attempts: Vec<Attempt>) -> History {
+        History { path: PathBuf::from("submissions.txt"), attempts }
+    }
+
+    #[test]
+    fn outcomes() {
+        let page = |text: &str| format!("<html><main>\n<article><p>{}</p></article>\n</main></html>", text);
+        let message = |text: &str| response_message(&page(text));
+        assert_eq!(parse_outcome(&message("That's the right answer! You are <em>one gold star</em> closer.")), Outcome::Correct);
+        assert_eq!(
+            parse_outcome(&message("That's not the right answer; your answer is too high. Please wait one minute.")),
+            Outcome::TooHigh,
+        );
+        assert_eq!(parse_outcome(&message("That's not the right answer; your answer is too low.")), Outcome::TooLow);
+        assert_eq!(parse_outcome(&message("That's not the right answer. If you're stuck...")), Outcome::Wrong);
+        assert_eq!(
+            parse_outcome(&message("You gave an answer too recently. You have 1m 20s left to wait.")),
+            Outcome::RateLimited(Duration::from_secs(80)),
+        );
+        assert_eq!(parse_outcome(&message("You have 7s left to wait.")), Outcome::RateLimited(Duration::from_secs(7)));
+        assert_eq!(
+            parse_outcome(&message("You don't seem to be solving the right level. Did you already complete it?")),
+            Outcome::AlreadySolved,
+        );
+        assert_eq!(parse_outcome(&message("Something else")), Outcome::Unknown);
+        assert_eq!(message("<b>a</b>\n  b"), "a b");
+    }
+
+    #[test]
+    fn history_roundtrip() {
+        let path = std::env::temp_dir().join(format!("aoc-submissions-{}.txt", std::process::id()));
+        let mut saved = History::load(&path).unwrap();
+        saved.record(attempt(10, "a \"b\"", Outcome::Wrong));
+        saved.record(attempt(20, "5", Outcome::RateLimited(Duration::from_secs(80))));
+        saved.save().unwrap();
+
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(loaded.attempts, saved.attempts);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn refuses_pointless_answers() {
+        let history = history(vec![
+            attempt(10, "100", Outcome::TooHigh),
+            attempt(20, "10", Outcome::TooLow),
+            attempt(30, "50", Outcome::Wrong),
+        ]);
+        assert!(history.check(2024, 1, 1, "42", 100).is_ok());
+        assert!(history.check(2024, 1, 2, "50", 100).is_ok());
+        assert!(history.check(2024, 1, 1, "50", 100).unwrap_err().to_string().contains("already submitted"));
+        assert!(history.check(2024, 1, 1, "100", 100).unwrap_err().to_string().contains("already submitted"));
+        assert!(history.check(2024, 1, 1, "150", 100).unwrap_err().to_string().contains("too high"));
+        assert!(history.check(2024, 1, 1, "3", 100).unwrap_err().to_string().contains("too low"));
+
+        let solved = self::history(vec![attempt(10, "7", Outcome::Correct)]);
+        assert!(solved.check(2024, 1, 1, "8", 100).unwrap_err().to_string().contains("already solved"));
+
+        let limited = self::history(vec![attempt(10, "7", Outcome::RateLimited(Duration::from_secs(60)))]);
+        assert!(limited.check(2024, 1, 1, "7", 30).unwrap_err().to_string().contains("try again in 40s"));
+        assert!(limited.check(2024, 1, 1, "7", 70).is_ok());
+    }
+
+    #[test]
+    fn submits_to_server() {
+        let (url, requests) = stub::serve(vec![
+            (200, "<article><p>That's not the right answer; your answer is too low.</p></article>"),
+            (200, "<article><p>That's the right answer!</p></article>"),
+        ]);
+        let config = Config { session: Some("secret".into()), contact: None, base_url: Some(url) };
+        let client = Client::new(&config).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc-submit-{}.txt", std::process::id()));
+        let mut history = History::load(&path).unwrap();
+
+        let (outcome, message) = submit_answer(&client, &mut history, 2024, 3, 2, "12").unwrap();
+        assert_eq!(outcome, Outcome::TooLow);
+        assert!(message.starts_with("That's not the right answer"));
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "POST /2024/day/3/answer HTTP/1.1");
+        assert_eq!(request.body, "level=2&answer=12");
+
+        // Refused locally, so the stub's second response is left for the next answer
+        assert!(submit_answer(&client, &mut history, 2024, 3, 2, "11").is_err());
+        assert_eq!(submit_answer(&client, &mut history, 2024, 3, 2, "13").unwrap().0, Outcome::Correct);
+
+        let saved = History::load(&path).unwrap();
+        let outcomes: Vec<_> = saved.attempts(2024, 3, 2).map(|a| (a.answer.as_str(), a.outcome.clone())).collect();
+        assert_eq!(outcomes, vec![("12", Outcome::TooLow), ("13", Outcome::Correct)]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn encodes_form() {
+        assert_eq!(form_encode("a b&c=1"), "a%20b%26c%3D1");
+    }
+}
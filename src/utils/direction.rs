@@ -1,6 +1,7 @@
 use crate::utils::Vec2;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// One of the four orthogonal directions on a map with y growing downwards.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Direction {
     Up,
     Down,
@@ -8,22 +9,47 @@ pub enum Direction {
     Right,
 }
 
-impl Direction {
-    pub fn from_char(c: char) -> Self {
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            'U' => Self::Up,
-            'D' => Self::Down,
-            'L' => Self::Left,
-            'R' => Self::Right,
-            '^' => Self::Up,
-            'v' => Self::Down,
-            'V' => Self::Down,
-            '<' => Self::Left,
-            '>' => Self::Right,
-            _ => panic!("Invalid direction"),
+            'U' => Ok(Self::Up),
+            'D' => Ok(Self::Down),
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            '^' => Ok(Self::Up),
+            'v' => Ok(Self::Down),
+            'V' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            '>' => Ok(Self::Right),
+            _ => anyhow::bail!("Invalid direction '{}'", c),
+        }
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.to_point()
+    }
+}
+
+/// Only unit steps along one axis are directions.
+impl TryFrom<Vec2> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(step: Vec2) -> Result<Self, Self::Error> {
+        match (step.x, step.y) {
+            (0, -1) => Ok(Self::Up),
+            (0, 1) => Ok(Self::Down),
+            (-1, 0) => Ok(Self::Left),
+            (1, 0) => Ok(Self::Right),
+            _ => anyhow::bail!("{:?} is not a step in one of four directions", step),
         }
     }
+}
 
+impl Direction {
     pub fn to_char(&self) -> char {
         match self {
             Self::Up => 'U',
@@ -33,6 +59,16 @@ impl Direction {
         }
     }
 
+    /// The arrow as maps draw it, `^`, `v`, `<` or `>`.
+    pub fn to_arrow(&self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Down => 'v',
+            Self::Left => '<',
+            Self::Right => '>',
+        }
+    }
+
     pub fn to_point(&self) -> Vec2 {
         match self {
             Self::Up => Vec2::new(0, -1),
@@ -63,4 +99,204 @@ impl Direction {
             Self::Right => [Self::Up, Self::Down],
         }
     }
-}
\ No newline at end of file
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// One of the eight directions to the cells around a position, including the diagonals.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Clockwise from `Up`, so turning is a step through this list.
+const CLOCKWISE: [Direction8; 8] = [
+    Direction8::Up,
+    Direction8::UpRight,
+    Direction8::Right,
+    Direction8::DownRight,
+    Direction8::Down,
+    Direction8::DownLeft,
+    Direction8::Left,
+    Direction8::UpLeft,
+];
+
+/// Takes the four-way characters and the arrows `↑`, `↗`, `→`, `↘`, `↓`, `↙`, `←` and `↖`.
+impl TryFrom<char> for Direction8 {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        if let Some(direction) = CLOCKWISE.iter().find(|d| d.to_char() == c) {
+            return Ok(*direction);
+        }
+        Direction::try_from(c).map(Self::from)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::Up,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+            Direction::Right => Self::Right,
+        }
+    }
+}
+
+/// Fails for the diagonals.
+impl TryFrom<Direction8> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(direction: Direction8) -> Result<Self, Self::Error> {
+        Direction::try_from(direction.to_point())
+    }
+}
+
+impl From<Direction8> for Vec2 {
+    fn from(direction: Direction8) -> Self {
+        direction.to_point()
+    }
+}
+
+/// Only steps of at most one along each axis are directions.
+impl TryFrom<Vec2> for Direction8 {
+    type Error = anyhow::Error;
+
+    fn try_from(step: Vec2) -> Result<Self, Self::Error> {
+        CLOCKWISE
+            .iter()
+            .find(|d| d.to_point() == step)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("{:?} is not a step in one of eight directions", step))
+    }
+}
+
+impl Direction8 {
+    /// All eight, clockwise from `Up`.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        CLOCKWISE.iter().copied()
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Self::Up => '↑',
+            Self::UpRight => '↗',
+            Self::Right => '→',
+            Self::DownRight => '↘',
+            Self::Down => '↓',
+            Self::DownLeft => '↙',
+            Self::Left => '←',
+            Self::UpLeft => '↖',
+        }
+    }
+
+    pub fn to_point(&self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::UpRight => Vec2::new(1, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::DownRight => Vec2::new(1, 1),
+            Self::Down => Vec2::new(0, 1),
+            Self::DownLeft => Vec2::new(-1, 1),
+            Self::Left => Vec2::new(-1, 0),
+            Self::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Self::UpRight | Self::DownRight | Self::DownLeft | Self::UpLeft)
+    }
+
+    fn turn(&self, eighths: usize) -> Self {
+        let index = CLOCKWISE.iter().position(|d| d == self).unwrap();
+        CLOCKWISE[(index + eighths) % CLOCKWISE.len()]
+    }
+
+    /// An eighth turn clockwise.
+    pub fn turn_right(&self) -> Self {
+        self.turn(1)
+    }
+
+    /// An eighth turn counter-clockwise.
+    pub fn turn_left(&self) -> Self {
+        self.turn(7)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn four_way() {
+        for direction in Direction::iter() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(Direction::try_from(Vec2::from(direction)).unwrap(), direction);
+            assert_eq!(Direction::try_from(direction.to_char()).unwrap(), direction);
+            assert_eq!(Direction::try_from(direction.to_arrow()).unwrap(), direction);
+            assert_eq!(direction.to_point() + direction.opposite().to_point(), Vec2::zero());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert!(Direction::try_from(Vec2::new(1, 1)).is_err());
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn eight_way() {
+        assert_eq!(Direction8::iter().count(), 8);
+        assert_eq!(Direction8::iter().filter(Direction8::is_diagonal).count(), 4);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.opposite(), Direction8::UpRight);
+        for direction in Direction8::iter() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(Direction8::try_from(Vec2::from(direction)).unwrap(), direction);
+            assert_eq!(Direction8::try_from(direction.to_char()).unwrap(), direction);
+            assert_eq!(direction.to_point() + direction.opposite().to_point(), Vec2::zero());
+            assert_eq!(Direction::try_from(direction).is_ok(), !direction.is_diagonal());
+        }
+        assert_eq!(Direction8::try_from('<').unwrap(), Direction8::Left);
+        assert_eq!(Direction8::from(Direction::Down), Direction8::Down);
+        assert!(Direction8::try_from(Vec2::new(2, 0)).is_err());
+    }
+}
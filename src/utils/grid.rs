@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use anyhow::{bail, Context};
+use crate::input::parse_lines;
+use crate::utils::direction::Direction8;
+use crate::utils::transform::Transform;
+use crate::utils::Vec2;
+
+/// A rectangular map of cells, stored row by row and addressed by `Vec2` with `x` as the column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Where the marker characters asked for in `Grid::parse_with_markers` were found, in reading order.
+#[derive(Clone, Debug, Default)]
+pub struct Markers(HashMap<char, Vec<Vec2>>);
+
+impl Markers {
+    /// All positions of `marker`.
+    pub fn all(&self, marker: char) -> &[Vec2] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The position of `marker`, which must appear exactly once.
+    pub fn single(&self, marker: char) -> anyhow::Result<Vec2> {
+        match self.all(marker) {
+            [pos] => Ok(*pos),
+            [] => bail!("Missing '{}' in map", marker),
+            more => bail!("Expected one '{}' in map, found {}", marker, more.len()),
+        }
+    }
+}
+
+fn points(width: usize, height: usize) -> impl Iterator<Item = Vec2> {
+    let (width, height) = (width as isize, height as isize);
+    (0..height).flat_map(move |y| (0..width).map(move |x| Vec2::new(x, y)))
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` by `height` from its cells row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "{}x{} grid needs {} cells", width, height, width * height);
+        Self { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// A grid from rows which all have to be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            bail!("Row {} is {} wide, expected {} like the first row", y + 1, row.len(), width);
+        }
+        let height = rows.len();
+        Ok(Self::new(width, height, rows.into_iter().flatten().collect()))
+    }
+
+    /// Parses one cell per character with `f`, one row per line.
+    pub fn parse(content: &str, mut f: impl FnMut(char) -> anyhow::Result<T>) -> anyhow::Result<Self> {
+        Self::parse_with_markers(content, "", |_, c| f(c)).map(|(grid, _)| grid)
+    }
+
+    /// Parses like `parse`, also recording where each character of `markers` is, e.g. `"SE"` for a start and an end.
+    /// `f` still decides what a marker stands on and gets the position of the character too.
+    pub fn parse_with_markers(
+        content: &str,
+        markers: &str,
+        mut f: impl FnMut(Vec2, char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<(Self, Markers)> {
+        let mut found = Markers::default();
+        let rows = parse_lines(content, |y, line| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    let pos = Vec2::new(x as isize, y as isize);
+                    if markers.contains(c) {
+                        found.0.entry(c).or_default().push(pos);
+                    }
+                    f(pos, c).with_context(|| format!("Invalid character '{}' at column {}", c, x + 1))
+                })
+                .collect()
+        })?;
+        Ok((Self::from_rows(rows)?, found))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn offset(&self, pos: Vec2) -> Option<usize> {
+        self.contains(pos).then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// The cell at `pos`, or `None` outside the grid.
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position of the grid in reading order. Does not borrow the grid, so cells can be changed meanwhile.
+    pub fn points(&self) -> impl Iterator<Item = Vec2> {
+        points(self.width, self.height)
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The first position whose cell matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Vec2> {
+        self.positions(predicate).next()
+    }
+
+    /// All positions whose cell matches `predicate`.
+    pub fn positions<'a>(&'a self, predicate: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = Vec2> + 'a {
+        self.iter().filter(move |(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    /// The up to four positions next to `pos` that are inside the grid, clockwise from above.
+    pub fn neighbours4(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.around(pos, Direction8::iter().filter(|direction| !direction.is_diagonal()))
+    }
+
+    /// Like `neighbours4`, including the diagonals.
+    pub fn neighbours8(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.around(pos, Direction8::iter())
+    }
+
+    fn around<'a>(&'a self, pos: Vec2, directions: impl Iterator<Item = Direction8> + 'a) -> impl Iterator<Item = Vec2> + 'a {
+        directions.map(move |direction| pos + direction.to_point()).filter(|next| self.contains(*next))
+    }
+
+    /// The grid turned and mirrored by `transform`. The result starts at `0, 0` again, so any translation is dropped.
+    pub fn transformed(&self, transform: &Transform) -> Self
+    where
+        T: Clone,
+    {
+        let (width, height) = if transform.swaps_axes() { (self.height, self.width) } else { (self.width, self.height) };
+        let last = Vec2::new(self.width as isize - 1, self.height as isize - 1);
+        let corners = [Vec2::zero(), Vec2::new(last.x, 0), Vec2::new(0, last.y), last].map(|c| transform.apply_linear(c));
+        let min = Vec2::new(corners.iter().map(|c| c.x).min().unwrap(), corners.iter().map(|c| c.y).min().unwrap());
+
+        // Look every new cell up where it came from, so each one is cloned exactly once
+        let inverse = transform.invert();
+        let cells = points(width, height).map(|pos| self[inverse.apply_linear(pos + min)].clone()).collect();
+        Self::new(width, height, cells)
+    }
+
+    /// Draws one character per cell, rows separated by newlines.
+    pub fn render(&self, mut f: impl FnMut(Vec2, &T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for (pos, cell) in self.iter() {
+            if pos.x == 0 && pos.y > 0 {
+                out.push('\n');
+            }
+            out.push(f(pos, cell));
+        }
+        out
+    }
+}
+
+/// Unchecked access, panics outside the grid.
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &T {
+        match self.offset(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is outside the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.offset(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (Grid<char>, Markers) {
+        Grid::parse_with_markers("#S.\n.#E\n..S", "SE", |_, c| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn parses_markers() {
+        let (grid, markers) = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(markers.single('E').unwrap(), Vec2::new(2, 1));
+        assert_eq!(markers.all('S'), &[Vec2::new(1, 0), Vec2::new(2, 2)]);
+        assert!(markers.single('S').is_err());
+        assert!(markers.single('@').is_err());
+        assert_eq!(grid.to_string(), "#S.\n.#E\n..S");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse("..\n.x", |c| if c == '.' { Ok(0) } else { bail!("not a dot") }).unwrap_err();
+        assert!(format!("{:#}", error).contains("line 2"), "{:#}", error);
+        assert!(Grid::parse("...\n..", Ok).is_err());
+    }
+
+    #[test]
+    fn indexing() {
+        let (mut grid, _) = sample();
+        assert_eq!(grid.get(Vec2::new(1, 1)), Some(&'#'));
+        assert_eq!(grid.get(Vec2::new(3, 0)), None);
+        assert_eq!(grid.get(Vec2::new(0, -1)), None);
+        grid[Vec2::new(0, 2)] = 'X';
+        assert_eq!(grid[Vec2::new(0, 2)], 'X');
+        *grid.get_mut(Vec2::new(2, 0)).unwrap() = 'Y';
+        assert_eq!(grid.render(|_, c| c.to_ascii_lowercase()), "#sy\n.#e\nx.s");
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 3x3 grid")]
+    fn unchecked_outside() {
+        let (grid, _) = sample();
+        let _ = grid[Vec2::new(-1, 0)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let (grid, _) = sample();
+        assert_eq!(grid.neighbours4(Vec2::new(0, 0)).collect::<Vec<_>>(), vec![Vec2::new(1, 0), Vec2::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Vec2::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Vec2::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Vec2::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::parse("ab\ncd\nef", Ok).unwrap();
+        assert_eq!(grid.transformed(&Transform::rotate_right()).to_string(), "eca\nfdb");
+        assert_eq!(grid.transformed(&Transform::rotate_left()).to_string(), "bdf\nace");
+        assert_eq!(grid.transformed(&Transform::rotation(2)).to_string(), "fe\ndc\nba");
+        assert_eq!(grid.transformed(&Transform::flip_x()).to_string(), "ba\ndc\nfe");
+        assert_eq!(grid.transformed(&Transform::transpose()).to_string(), "ace\nbdf");
+        let moved = Transform::flip_y().then(&Transform::translate(Vec2::new(5, 5)));
+        assert_eq!(grid.transformed(&moved), grid.transformed(&Transform::flip_y()));
+        for transform in Transform::orientations() {
+            assert_eq!(grid.transformed(&transform).transformed(&transform.invert()), grid);
+        }
+    }
+
+    #[test]
+    fn searching() {
+        let (grid, _) = sample();
+        assert_eq!(grid.find(|&c| c == '#'), Some(Vec2::new(0, 0)));
+        assert_eq!(grid.find(|&c| c == '?'), None);
+        assert_eq!(grid.positions(|&c| c == '.').count(), 4);
+        assert_eq!(grid.points().count(), 9);
+    }
+}
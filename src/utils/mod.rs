@@ -1,184 +1,112 @@
-pub mod term;
-pub mod direction;
-
-use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Mul, Rem};
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Vec2<T = isize> {
-    pub x: T,
-    pub y: T,
-}
-
-impl Debug for Vec2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "V({}, {})", self.x, self.y)
-    }
-}
-
-impl Add for Vec2 {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
+/// Implements the arithmetic of a vector type from its `map` and `zip_with`: component-wise `+`, `-` and `%` (wrapping
+/// with `rem_euclid`) between vectors, `*` and `/` by a scalar, `-` for signed types, and their assigning forms.
+/// The type parameters go in brackets, e.g. `vector_ops!([T, const N: usize] VecN<T, N>)`, with `T` the component.
+macro_rules! vector_ops {
+    ([$($params:tt)*] $ty:ty) => {
+        vector_ops!(@vector [$($params)*] $ty, Add, add, AddAssign, add_assign, |a, b| a + b);
+        vector_ops!(@vector [$($params)*] $ty, Sub, sub, SubAssign, sub_assign, |a, b| a - b);
+        // Wraps into `0..other` per axis, also for negative components, e.g. to keep positions on a torus
+        vector_ops!(@vector [$($params)*] $ty, Rem, rem, RemAssign, rem_assign, |a, b| a.rem_euclid(b));
+        vector_ops!(@scalar [$($params)*] $ty, Mul, mul, MulAssign, mul_assign);
+        vector_ops!(@scalar [$($params)*] $ty, Div, div, DivAssign, div_assign);
+
+        impl<$($params)*> std::ops::Neg for $ty where T: Int + std::ops::Neg<Output = T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                self.map(|a| -a)
+            }
         }
-    }
-}
 
-impl Add<&Vec2> for Vec2 {
-    type Output = Self;
+        impl<$($params)*> std::ops::Neg for &$ty where T: Int + std::ops::Neg<Output = T> {
+            type Output = $ty;
 
-    fn add(self, other: &Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
+            fn neg(self) -> $ty {
+                -*self
+            }
         }
-    }
-}
-
-impl Add<Vec2> for &Vec2 {
-    type Output = Vec2;
-
-    fn add(self, other: Vec2) -> Vec2 {
-        Vec2 {
-            x: self.x + other.x,
-            y: self.y + other.y,
+    };
+    (@vector [$($params:tt)*] $ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident,
+     |$a:ident, $b:ident| $op:expr) => {
+        impl<$($params)*> std::ops::$trait for $ty where T: Int {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.zip_with(other, |$a, $b| $op)
+            }
         }
-    }
-}
 
-impl Add<&Vec2> for &Vec2 {
-    type Output = Vec2;
+        impl<$($params)*> std::ops::$trait<&$ty> for $ty where T: Int {
+            type Output = Self;
 
-    fn add(self, other: &Vec2) -> Vec2 {
-        Vec2 {
-            x: self.x + other.x,
-            y: self.y + other.y,
+            fn $method(self, other: &Self) -> Self {
+                std::ops::$trait::$method(self, *other)
+            }
         }
-    }
-}
-
 
-impl Mul<isize> for &Vec2<isize>
-{
-    type Output = Vec2<isize>;
+        impl<$($params)*> std::ops::$trait<$ty> for &$ty where T: Int {
+            type Output = $ty;
 
-    fn mul(self, rhs: isize) -> Self::Output {
-        Vec2 { x: self.x * rhs, y: self.y * rhs }
-    }
-}
-
-
-impl AddAssign<&Vec2> for Vec2 {
-    fn add_assign(&mut self, rhs: &Vec2) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-
-impl AddAssign<Vec2> for Vec2 {
-    fn add_assign(&mut self, rhs: Vec2) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-impl Rem<Self> for Vec2 {
-    type Output = Self;
+            fn $method(self, other: $ty) -> $ty {
+                std::ops::$trait::$method(*self, other)
+            }
+        }
 
-    fn rem(self, other: Self) -> Self {
-        let mut new_x = self.x % other.x;
-        let mut new_y = self.y % other.y;
+        impl<$($params)*> std::ops::$trait<&$ty> for &$ty where T: Int {
+            type Output = $ty;
 
-        while new_x < 0 {
-            new_x += other.x;
+            fn $method(self, other: &$ty) -> $ty {
+                std::ops::$trait::$method(*self, *other)
+            }
         }
 
-        while new_y < 0 {
-            new_y += other.y;
+        impl<$($params)*> std::ops::$assign_trait for $ty where T: Int {
+            fn $assign_method(&mut self, other: Self) {
+                *self = std::ops::$trait::$method(*self, other);
+            }
         }
 
-        Self {
-            x: new_x,
-            y: new_y,
+        impl<$($params)*> std::ops::$assign_trait<&$ty> for $ty where T: Int {
+            fn $assign_method(&mut self, other: &Self) {
+                *self = std::ops::$trait::$method(*self, *other);
+            }
         }
-    }
-}
+    };
+    (@scalar [$($params:tt)*] $ty:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl<$($params)*> std::ops::$trait<T> for $ty where T: Int {
+            type Output = Self;
+
+            fn $method(self, rhs: T) -> Self {
+                self.map(|a| std::ops::$trait::$method(a, rhs))
+            }
+        }
+
+        impl<$($params)*> std::ops::$trait<T> for &$ty where T: Int {
+            type Output = $ty;
 
+            fn $method(self, rhs: T) -> $ty {
+                std::ops::$trait::$method(*self, rhs)
+            }
+        }
 
-impl Vec2 {
-    pub fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-
-    pub fn parse_with_regex(input: &str, regex: &regex::Regex) -> Self {
-        let captures = regex.captures(input).unwrap();
-        let x = captures.get(1).unwrap().as_str().parse().unwrap();
-        let y = captures.get(2).unwrap().as_str().parse().unwrap();
-        Self { x, y }
-    }
-
-    pub fn length(&self) -> f64 {
-        ((self.x * self.x + self.y * self.y) as f64).sqrt()
-    }
-
-    pub fn distance(&self, other: &Self) -> f64 {
-        let x = (self.x - other.x) as f64;
-        let y = (self.y - other.y) as f64;
-        (x * x + y * y).sqrt()
-    }
-
-    pub fn manhattan_length(&self) -> isize {
-        self.x.abs() + self.y.abs()
-    }
-
-    pub fn manhattan_distance(&self, other: &Self) -> isize {
-        (self.x.abs_diff(other.x) + self.y.abs_diff(other.x)) as isize
-    }
-
-    pub fn rotate(&self, degrees: isize) -> Self {
-        let radians = degrees as f64 * std::f64::consts::PI / 180.0;
-        let x = (self.x as f64 * radians.cos() - self.y as f64 * radians.sin()).round() as isize;
-        let y = (self.x as f64 * radians.sin() + self.y as f64 * radians.cos()).round() as isize;
-        Self { x, y }
-    }
-
-    pub fn move_towards(&self, direction: direction::Direction) -> Self {
-        let vec = direction.to_point();
-        self + vec
-    }
-
-    pub fn zero() -> Self {
-        Self { x: 0, y: 0 }
-    }
+        impl<$($params)*> std::ops::$assign_trait<T> for $ty where T: Int {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = std::ops::$trait::$method(*self, rhs);
+            }
+        }
+    };
 }
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn modulo_test_noop() {
-        let v = super::Vec2::new(3, 3);
-        let f = super::Vec2::new(5, 5);
-        let r = v % f;
-        assert_eq!(r, super::Vec2::new(3, 3));
-    }
-
-    #[test]
-    fn modulo_test_negative() {
-        let v = super::Vec2::new(-3, -3);
-        let f = super::Vec2::new(5, 5);
-        let r = v % f;
-        assert_eq!(r, super::Vec2::new(2, 2));
-    }
-
-    #[test]
-    fn modulo_test_wrap() {
-        let v = super::Vec2::new(3, 3);
-        let f = super::Vec2::new(2, 2);
-        let r = v % f;
-        assert_eq!(r, super::Vec2::new(1, 1));
-    }
-}
\ No newline at end of file
+pub mod term;
+pub mod direction;
+pub mod grid;
+pub mod num;
+pub mod transform;
+pub mod vec2;
+pub mod vec3;
+pub mod vecn;
+
+pub use vec2::Vec2;
+pub use vec3::Vec3;
+pub use transform::Transform;
+pub use vecn::VecN;
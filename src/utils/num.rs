@@ -0,0 +1,151 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The primitive integer types, so vectors and grids can be generic over them.
+pub trait Int:
+    Copy
+    + Default
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + FromStr<Err = ParseIntError>
+    + TryFrom<usize>
+    + TryInto<usize>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The remainder that is never negative for a positive `rhs`, e.g. `-1 mod 5 = 4`.
+    fn rem_euclid(self, rhs: Self) -> Self;
+    /// The absolute value, which unsigned types already are.
+    fn abs(self) -> Self;
+    /// `-1`, `0` or `1`, never `-1` for unsigned types.
+    fn signum(self) -> Self;
+    /// `|self - other|` without overflowing below zero for unsigned types. Panics when the distance does not fit `Self`,
+    /// which only happens for signed types with the two on either side of zero, e.g. `-100i8` and `100i8`.
+    fn abs_diff(self, other: Self) -> Self {
+        let (high, low) = if self > other { (self, other) } else { (other, self) };
+        high.checked_sub(low)
+            .unwrap_or_else(|| panic!("The distance from {} to {} does not fit in {}", self, other, std::any::type_name::<Self>()))
+    }
+    fn as_f64(self) -> f64;
+    /// `self + rhs`, or `None` when it does not fit.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// `self - rhs`, or `None` when it does not fit, e.g. below zero for unsigned types.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    (signed: $($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+    (unsigned: $($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            fn abs(self) -> Self {
+                self
+            }
+
+            fn signum(self) -> Self {
+                (self > 0) as $t
+            }
+
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_int!(signed: i8, i16, i32, i64, i128, isize);
+impl_int!(unsigned: u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::Int;
+
+    #[test]
+    fn signed_and_unsigned() {
+        assert_eq!(Int::rem_euclid(-7i32, 5), 3);
+        assert_eq!(Int::rem_euclid(7u8, 5), 2);
+        assert_eq!(Int::abs(-3i64), 3);
+        assert_eq!(Int::abs(3u64), 3);
+        assert_eq!(Int::signum(-3isize), -1);
+        assert_eq!(Int::signum(0u16), 0);
+        assert_eq!(Int::signum(9u16), 1);
+        assert_eq!(Int::abs_diff(2u32, 7), 5);
+        assert_eq!(Int::abs_diff(-2i32, 7), 9);
+        assert_eq!(Int::checked_sub(0u8, 1), None);
+        assert_eq!(Int::checked_add(127i8, 1), None);
+        assert_eq!(Int::checked_sub(0i8, 1), Some(-1));
+    }
+
+    #[test]
+    fn abs_diff_at_the_limits() {
+        assert_eq!(Int::abs_diff(i8::MIN, -1), 127);
+        assert_eq!(Int::abs_diff(i8::MAX, 0), 127);
+        assert_eq!(Int::abs_diff(i64::MIN, i64::MIN), 0);
+        assert_eq!(Int::abs_diff(u8::MAX, 0), 255);
+        assert_eq!(Int::abs_diff(0u8, u8::MAX), 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in i8")]
+    fn abs_diff_overflow() {
+        Int::abs_diff(-100i8, 100);
+    }
+}
@@ -0,0 +1,161 @@
+use std::ops::Neg;
+use crate::utils::num::Int;
+use crate::utils::Vec2;
+
+/// A combination of quarter turns, reflections and a translation, applied as `matrix * p + offset`. Only those can be
+/// built, so the result stays exact on integers and every transform can be inverted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transform<T = isize> {
+    // Rows of the matrix, all entries are -1, 0 or 1
+    x_row: Vec2<T>,
+    y_row: Vec2<T>,
+    offset: Vec2<T>,
+}
+
+impl<T: Int + Neg<Output = T>> Default for Transform<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: Int + Neg<Output = T>> Transform<T> {
+    pub fn identity() -> Self {
+        Self::linear(Vec2::new(T::ONE, T::ZERO), Vec2::new(T::ZERO, T::ONE))
+    }
+
+    fn linear(x_row: Vec2<T>, y_row: Vec2<T>) -> Self {
+        Self { x_row, y_row, offset: Vec2::zero() }
+    }
+
+    /// Maps every axis of the identity through `f`, which has to be one of the `Vec2` turns or reflections.
+    fn from_fn(f: impl Fn(Vec2<T>) -> Vec2<T>) -> Self {
+        let x = f(Vec2::new(T::ONE, T::ZERO));
+        let y = f(Vec2::new(T::ZERO, T::ONE));
+        // The images of the axes are the columns
+        Self::linear(Vec2::new(x.x, y.x), Vec2::new(x.y, y.y))
+    }
+
+    /// A quarter turn clockwise about the origin, like `Vec2::rotate_right`.
+    pub fn rotate_right() -> Self {
+        Self::from_fn(|v| v.rotate_right())
+    }
+
+    /// A quarter turn counter-clockwise about the origin.
+    pub fn rotate_left() -> Self {
+        Self::from_fn(|v| v.rotate_left())
+    }
+
+    /// `quarters` quarter turns clockwise about the origin.
+    pub fn rotation(quarters: i32) -> Self {
+        Self::from_fn(|v| v.rotate_quarters(quarters))
+    }
+
+    /// Negates `x`, mirroring left and right.
+    pub fn flip_x() -> Self {
+        Self::from_fn(|v| v.flip_x())
+    }
+
+    /// Negates `y`, mirroring up and down.
+    pub fn flip_y() -> Self {
+        Self::from_fn(|v| v.flip_y())
+    }
+
+    /// Swaps `x` and `y`.
+    pub fn transpose() -> Self {
+        Self::from_fn(|v| v.transpose())
+    }
+
+    pub fn translate(offset: Vec2<T>) -> Self {
+        Self { offset, ..Self::identity() }
+    }
+
+    /// The eight ways to turn and mirror a square, e.g. to try every orientation of a tile.
+    pub fn orientations() -> impl Iterator<Item = Self> {
+        (0..4).flat_map(|quarters| [Self::rotation(quarters), Self::rotation(quarters).then(&Self::flip_x())])
+    }
+
+    pub fn apply(&self, p: Vec2<T>) -> Vec2<T> {
+        self.apply_linear(p) + self.offset
+    }
+
+    /// Applies the turns and reflections without the translation, e.g. to a direction.
+    pub fn apply_linear(&self, p: Vec2<T>) -> Vec2<T> {
+        Vec2::new(self.x_row.dot(&p), self.y_row.dot(&p))
+    }
+
+    /// The transform doing `self` first and `next` after it.
+    pub fn then(&self, next: &Self) -> Self {
+        let columns = (self.apply_linear(Vec2::new(T::ONE, T::ZERO)), self.apply_linear(Vec2::new(T::ZERO, T::ONE)));
+        let (x, y) = (next.apply_linear(columns.0), next.apply_linear(columns.1));
+        Self {
+            x_row: Vec2::new(x.x, y.x),
+            y_row: Vec2::new(x.y, y.y),
+            offset: next.apply(self.offset),
+        }
+    }
+
+    /// The transform undoing this one.
+    pub fn invert(&self) -> Self {
+        // The matrix is orthogonal, so its inverse is its transpose
+        let linear = Self::linear(Vec2::new(self.x_row.x, self.y_row.x), Vec2::new(self.x_row.y, self.y_row.y));
+        Self { offset: -linear.apply_linear(self.offset), ..linear }
+    }
+
+    /// Whether the matrix swaps the axes, which makes a grid's width its height.
+    pub fn swaps_axes(&self) -> bool {
+        self.x_row.x == T::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn matches_vec2() {
+        let p: Vec2 = Vec2::new(2, -5);
+        assert_eq!(Transform::rotate_right().apply(p), p.rotate_right());
+        assert_eq!(Transform::rotate_left().apply(p), p.rotate_left());
+        assert_eq!(Transform::rotation(2).apply(p), -p);
+        assert_eq!(Transform::flip_x().apply(p), p.flip_x());
+        assert_eq!(Transform::flip_y().apply(p), p.flip_y());
+        assert_eq!(Transform::transpose().apply(p), p.transpose());
+        assert_eq!(Transform::translate(Vec2::new(1, 1)).apply(p), Vec2::new(3, -4));
+        assert_eq!(Transform::identity().apply(p), p);
+    }
+
+    #[test]
+    fn composes_in_order() {
+        let p: Vec2<i32> = Vec2::new(3, 1);
+        let turn_then_move = Transform::rotate_right().then(&Transform::translate(Vec2::new(10, 0)));
+        assert_eq!(turn_then_move.apply(p), Vec2::new(9, 3));
+        let move_then_turn = Transform::translate(Vec2::new(10, 0)).then(&Transform::rotate_right());
+        assert_eq!(move_then_turn.apply(p), Vec2::new(-1, 13));
+
+        let four: Transform = (0..4).fold(Transform::identity(), |t, _| t.then(&Transform::rotate_right()));
+        assert_eq!(four, Transform::identity());
+        let mirrored: Transform = Transform::flip_x().then(&Transform::flip_y());
+        assert_eq!(mirrored, Transform::rotation(2));
+        let turned: Transform = Transform::transpose().then(&Transform::flip_x());
+        assert_eq!(turned, Transform::rotate_right());
+    }
+
+    #[test]
+    fn inverts() {
+        let t: Transform = Transform::flip_y().then(&Transform::rotate_left()).then(&Transform::translate(Vec2::new(4, -2)));
+        let p = Vec2::new(7, 3);
+        assert_eq!(t.invert().apply(t.apply(p)), p);
+        assert_eq!(t.then(&t.invert()), Transform::identity());
+        assert_eq!(t.invert().then(&t), Transform::identity());
+    }
+
+    #[test]
+    fn eight_orientations() {
+        let all: HashSet<Transform> = Transform::orientations().collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all.iter().filter(|t| t.swaps_axes()).count(), 4);
+        assert!(all.contains(&Transform::transpose()));
+        assert!(all.contains(&Transform::flip_y()));
+    }
+}
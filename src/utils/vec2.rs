@@ -0,0 +1,355 @@
+use std::fmt::Debug;
+use anyhow::Context;
+use crate::utils::direction::Direction;
+use crate::utils::num::Int;
+use crate::utils::VecN;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vec2<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Debug> Debug for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "V({:?}, {:?})", self.x, self.y)
+    }
+}
+
+vector_ops!([T] Vec2<T>);
+
+/// Fails for negative coordinates or ones that do not fit a `usize`.
+impl<T: Int> TryFrom<Vec2<T>> for (usize, usize) {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Vec2<T>) -> Result<Self, Self::Error> {
+        let convert = |n: T| n.try_into().ok().with_context(|| format!("{:?} is not a valid index", value));
+        Ok((convert(value.x)?, convert(value.y)?))
+    }
+}
+
+/// Fails when a coordinate does not fit `T`.
+impl<T: Int> TryFrom<(usize, usize)> for Vec2<T> {
+    type Error = anyhow::Error;
+
+    fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+        let convert = |n: usize| {
+            T::try_from(n).ok().with_context(|| format!("({}, {}) does not fit in {}", x, y, std::any::type_name::<T>()))
+        };
+        Ok(Vec2 { x: convert(x)?, y: convert(y)? })
+    }
+}
+
+impl<T: Int> From<Vec2<T>> for VecN<T, 2> {
+    fn from(v: Vec2<T>) -> Self {
+        VecN([v.x, v.y])
+    }
+}
+
+impl<T: Int> From<VecN<T, 2>> for Vec2<T> {
+    fn from(VecN([x, y]): VecN<T, 2>) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Self { x: f(self.x), y: f(self.y) }
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        Self { x: f(self.x, other.x), y: f(self.y, other.y) }
+    }
+}
+
+impl<T: Int> Vec2<T> {
+    pub fn zero() -> Self {
+        Self { x: T::ZERO, y: T::ZERO }
+    }
+
+    pub fn parse_with_regex(input: &str, regex: &regex::Regex) -> anyhow::Result<Self> {
+        let captures = regex.captures(input)
+            .with_context(|| format!("`{}` does not match {}", input, regex))?;
+        let x = captures.get(1).context("Missing x coordinate")?.as_str().parse()?;
+        let y = captures.get(2).context("Missing y coordinate")?.as_str().parse()?;
+        Ok(Self { x, y })
+    }
+
+    pub fn length(&self) -> f64 {
+        let (x, y) = (self.x.as_f64(), self.y.as_f64());
+        (x * x + y * y).sqrt()
+    }
+
+    pub fn distance(&self, other: &Self) -> f64 {
+        let x = self.x.abs_diff(other.x).as_f64();
+        let y = self.y.abs_diff(other.y).as_f64();
+        (x * x + y * y).sqrt()
+    }
+
+    pub fn manhattan_length(&self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of king moves between the two, i.e. the larger distance along one axis.
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, positive when `other` is counter-clockwise of `self` with y up.
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Each component as `-1`, `0` or `1`, e.g. one step along a straight line.
+    pub fn signum(&self) -> Self {
+        Self { x: self.x.signum(), y: self.y.signum() }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self { x: self.x.abs(), y: self.y.abs() }
+    }
+
+    /// The up to 4 points sharing an edge with this one. Unlike `Grid::neighbours4` these are not limited to a map.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Self> {
+        VecN::from(*self).axis_neighbours().map(Self::from)
+    }
+
+    /// The up to 8 points sharing an edge or corner with this one.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Self> {
+        VecN::from(*self).neighbours().map(Self::from)
+    }
+}
+
+/// Exact turns and reflections about the origin. With y growing downwards as on a map, turning right is clockwise.
+impl<T: Int + std::ops::Neg<Output = T>> Vec2<T> {
+    /// A quarter turn clockwise, e.g. from up `(0, -1)` to right `(1, 0)`.
+    pub fn rotate_right(&self) -> Self {
+        Self { x: -self.y, y: self.x }
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        Self { x: self.y, y: -self.x }
+    }
+
+    /// `quarters` quarter turns clockwise, counter-clockwise for negative `quarters`.
+    pub fn rotate_quarters(&self, quarters: i32) -> Self {
+        match quarters.rem_euclid(4) {
+            0 => *self,
+            1 => self.rotate_right(),
+            2 => -*self,
+            _ => self.rotate_left(),
+        }
+    }
+
+    /// Mirrors across the y axis, negating `x`.
+    pub fn flip_x(&self) -> Self {
+        Self { x: -self.x, y: self.y }
+    }
+
+    /// Mirrors across the x axis, negating `y`.
+    pub fn flip_y(&self) -> Self {
+        Self { x: self.x, y: -self.y }
+    }
+
+    /// Mirrors across the diagonal through the origin, swapping `x` and `y`.
+    pub fn transpose(&self) -> Self {
+        Self { x: self.y, y: self.x }
+    }
+}
+
+impl Vec2 {
+    pub fn move_towards(&self, direction: Direction) -> Self {
+        let vec = direction.to_point();
+        self + vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vec2;
+
+    #[test]
+    fn modulo_test_noop() {
+        let v = Vec2::new(3, 3);
+        let f = Vec2::new(5, 5);
+        let r = v % f;
+        assert_eq!(r, Vec2::new(3, 3));
+    }
+
+    #[test]
+    fn modulo_test_negative() {
+        let v = Vec2::new(-3, -3);
+        let f = Vec2::new(5, 5);
+        let r = v % f;
+        assert_eq!(r, Vec2::new(2, 2));
+    }
+
+    #[test]
+    fn modulo_test_wrap() {
+        let v = Vec2::new(3, 3);
+        let f = Vec2::new(2, 2);
+        let r = v % f;
+        assert_eq!(r, Vec2::new(1, 1));
+    }
+
+    #[test]
+    fn modulo_far_negative() {
+        let mut v: Vec2 = Vec2::new(-13, 7);
+        v %= Vec2::new(5, 3);
+        assert_eq!(v, Vec2::new(2, 1));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)] // Every mix of owned and borrowed operands has its own impl
+    fn add_and_sub() {
+        let a: Vec2<i32> = Vec2::new(1, 2);
+        let b = Vec2::new(10, -20);
+        assert_eq!(a + b, Vec2::new(11, -18));
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(&a + b, a + &b);
+        assert_eq!(b - a, Vec2::new(9, -22));
+        assert_eq!(&b - &a, b - &a);
+        assert_eq!(&b - a, b - a);
+
+        let mut c = a;
+        c += b;
+        c -= &a;
+        assert_eq!(c, b);
+        c += &a;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn unsigned() {
+        let a: Vec2<u8> = Vec2::new(3, 250);
+        assert_eq!(a + Vec2::new(2, 5), Vec2::new(5, 255));
+        assert_eq!(a - Vec2::new(3, 50), Vec2::new(0, 200));
+        assert_eq!(a % Vec2::new(2, 7), Vec2::new(1, 5));
+        assert_eq!(a.manhattan_distance(&Vec2::new(5, 200)), 52);
+        assert_eq!(a.signum(), Vec2::new(1, 1));
+        assert_eq!(Vec2::<u64>::zero().signum(), Vec2::zero());
+    }
+
+    #[test]
+    fn neighbours() {
+        let a: Vec2 = Vec2::new(3, -2);
+        let edges: Vec<_> = a.neighbours4().collect();
+        assert_eq!(edges.len(), 4);
+        assert!(edges.iter().all(|n| n.manhattan_distance(&a) == 1));
+        let all: Vec<_> = a.neighbours8().collect();
+        assert_eq!(all.len(), 8);
+        assert!(all.iter().all(|n| n.chebyshev_distance(&a) == 1));
+
+        let corner: Vec2<usize> = Vec2::zero();
+        assert_eq!(corner.neighbours4().count(), 2);
+        assert_eq!(corner.neighbours8().count(), 3);
+    }
+
+    #[test]
+    fn neg() {
+        let a: Vec2<i64> = Vec2::new(4, -5);
+        assert_eq!(-a, Vec2::new(-4, 5));
+        assert_eq!(-&a, -a);
+        assert_eq!(a + -a, Vec2::zero());
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn scalar_mul_and_div() {
+        let a: Vec2 = Vec2::new(3, -4);
+        assert_eq!(a * 3, Vec2::new(9, -12));
+        assert_eq!(&a * -1, Vec2::new(-3, 4));
+        assert_eq!(Vec2::new(9, -12) / 3, a);
+        assert_eq!(&Vec2::new(7, -7) / 2, Vec2::new(3, -3));
+
+        let mut b = a;
+        b *= 2;
+        assert_eq!(b, Vec2::new(6, -8));
+        b /= 2;
+        assert_eq!(b, a);
+    }
+
+    #[test]
+    fn usize_conversions() {
+        let v: Vec2<i16> = Vec2::try_from((3usize, 4usize)).unwrap();
+        assert_eq!(v, Vec2::new(3, 4));
+        assert_eq!(<(usize, usize)>::try_from(v).unwrap(), (3, 4));
+        assert!(<(usize, usize)>::try_from(Vec2::new(-1, 4)).is_err());
+        assert!(Vec2::<i8>::try_from((200usize, 0usize)).is_err());
+        assert!(Vec2::<u8>::try_from((255usize, 0usize)).is_ok());
+    }
+
+    #[test]
+    fn products() {
+        let a: Vec2 = Vec2::new(2, 3);
+        let b = Vec2::new(-4, 5);
+        assert_eq!(a.dot(&b), 7);
+        assert_eq!(a.cross(&b), 22);
+        assert_eq!(b.cross(&a), -22);
+        assert_eq!(a.cross(&(a * 2)), 0);
+    }
+
+    #[test]
+    fn signum_and_abs() {
+        let a: Vec2<i32> = Vec2::new(-7, 0);
+        assert_eq!(a.signum(), Vec2::new(-1, 0));
+        assert_eq!(a.abs(), Vec2::new(7, 0));
+        assert_eq!(Vec2::new(3, -9).signum(), Vec2::new(1, -1));
+    }
+
+    #[test]
+    fn distances() {
+        let a: Vec2 = Vec2::new(1, 10);
+        let b = Vec2::new(4, 6);
+        // `y` used to be compared against the other `x`
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(b.manhattan_distance(&a), 7);
+        assert_eq!(Vec2::new(-3, 4).manhattan_length(), 7);
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(Vec2::new(3, -4).length(), 5.0);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.chebyshev_distance(&a), 0);
+    }
+
+    #[test]
+    fn rotations() {
+        let up: Vec2 = Vec2::new(0, -1);
+        assert_eq!(up.rotate_right(), Vec2::new(1, 0));
+        assert_eq!(up.rotate_left(), Vec2::new(-1, 0));
+        assert_eq!(up.rotate_quarters(2), Vec2::new(0, 1));
+        assert_eq!(up.rotate_quarters(-1), up.rotate_left());
+
+        let v: Vec2<i32> = Vec2::new(3, -7);
+        assert_eq!(v.rotate_quarters(4), v);
+        assert_eq!(v.rotate_quarters(-3), v.rotate_right());
+        assert_eq!(v.rotate_right().rotate_left(), v);
+        assert_eq!(v.rotate_quarters(3), v.rotate_left());
+        // Exact for values where floats would lose precision
+        let big: Vec2<i64> = Vec2::new(i64::MAX - 1, 1);
+        assert_eq!(big.rotate_right(), Vec2::new(-1, i64::MAX - 1));
+    }
+
+    #[test]
+    fn reflections() {
+        let v: Vec2 = Vec2::new(2, 5);
+        assert_eq!(v.flip_x(), Vec2::new(-2, 5));
+        assert_eq!(v.flip_y(), Vec2::new(2, -5));
+        assert_eq!(v.transpose(), Vec2::new(5, 2));
+        assert_eq!(v.flip_x().flip_y(), v.rotate_quarters(2));
+        assert_eq!(v.transpose().flip_x(), v.rotate_right());
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Debug;
+use crate::utils::num::Int;
+use crate::utils::VecN;
+
+/// A point or offset in 3D, e.g. a voxel. Works through `VecN` for anything that is not specific to three axes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vec3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Debug> Debug for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "V({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+}
+
+vector_ops!([T] Vec3<T>);
+
+impl<T: Int> From<Vec3<T>> for VecN<T, 3> {
+    fn from(v: Vec3<T>) -> Self {
+        VecN([v.x, v.y, v.z])
+    }
+}
+
+impl<T: Int> From<VecN<T, 3>> for Vec3<T> {
+    fn from(VecN([x, y, z]): VecN<T, 3>) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Self { x: f(self.x), y: f(self.y), z: f(self.z) }
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        Self { x: f(self.x, other.x), y: f(self.y, other.y), z: f(self.z, other.z) }
+    }
+}
+
+impl<T: Int> Vec3<T> {
+    pub fn zero() -> Self {
+        Self { x: T::ZERO, y: T::ZERO, z: T::ZERO }
+    }
+
+    /// Parses `x`, `y` and `z` from the first three capture groups of `regex`.
+    pub fn parse_with_regex(input: &str, regex: &regex::Regex) -> anyhow::Result<Self> {
+        VecN::parse_with_regex(input, regex).map(Self::from)
+    }
+
+    pub fn manhattan_length(&self) -> T {
+        VecN::from(*self).manhattan_length()
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        VecN::from(*self).manhattan_distance(&(*other).into())
+    }
+
+    /// The largest distance along one axis, i.e. the number of steps when diagonals count as one.
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        VecN::from(*self).chebyshev_distance(&(*other).into())
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The vector perpendicular to both, following the right-hand rule.
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn signum(&self) -> Self {
+        self.map(Int::signum)
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(Int::abs)
+    }
+
+    /// The up to 6 points sharing a face with this one.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Self> {
+        VecN::from(*self).axis_neighbours().map(Self::from)
+    }
+
+    /// The up to 26 points sharing a face, edge or corner with this one.
+    pub fn neighbours26(&self) -> impl Iterator<Item = Self> {
+        VecN::from(*self).neighbours().map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use lazy_static::lazy_static;
+    use regex::Regex;
+    use super::Vec3;
+
+    #[test]
+    #[allow(clippy::op_ref)] // Every mix of owned and borrowed operands has its own impl
+    fn arithmetic() {
+        let a: Vec3 = Vec3::new(1, 2, 3);
+        let b = Vec3::new(-4, 0, 7);
+        assert_eq!(a + b, Vec3::new(-3, 2, 10));
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(a - &b, Vec3::new(5, 2, -4));
+        assert_eq!(&a - b, a - b);
+        assert_eq!(-a, Vec3::new(-1, -2, -3));
+        assert_eq!(-&b, Vec3::new(4, 0, -7));
+        assert_eq!(a * 3, Vec3::new(3, 6, 9));
+        assert_eq!(&b / 2, Vec3::new(-2, 0, 3));
+        assert_eq!(b % Vec3::new(3, 3, 3), Vec3::new(2, 0, 1));
+
+        let mut c = a;
+        c += b;
+        c -= &b;
+        c *= 2;
+        c /= 2;
+        c %= Vec3::new(2, 2, 2);
+        assert_eq!(c, Vec3::new(1, 0, 1));
+    }
+
+    #[test]
+    fn products() {
+        let x: Vec3<i32> = Vec3::new(1, 0, 0);
+        let y = Vec3::new(0, 1, 0);
+        assert_eq!(x.cross(&y), Vec3::new(0, 0, 1));
+        assert_eq!(y.cross(&x), Vec3::new(0, 0, -1));
+        assert_eq!(Vec3::new(1, 2, 3).dot(&Vec3::new(4, -5, 6)), 12);
+        assert_eq!(Vec3::new(-3, 0, 8).signum(), Vec3::new(-1, 0, 1));
+        assert_eq!(Vec3::new(-3, 0, 8).abs(), Vec3::new(3, 0, 8));
+    }
+
+    #[test]
+    fn distances() {
+        let a: Vec3 = Vec3::new(1, 1, 1);
+        let b = Vec3::new(2, -2, 6);
+        assert_eq!(a.manhattan_distance(&b), 9);
+        assert_eq!(a.chebyshev_distance(&b), 5);
+        assert_eq!(b.manhattan_length(), 10);
+    }
+
+    #[test]
+    fn neighbours() {
+        let a: Vec3 = Vec3::new(1, 2, 3);
+        let faces: HashSet<_> = a.neighbours6().collect();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|n| n.manhattan_distance(&a) == 1));
+
+        let all: HashSet<_> = a.neighbours26().collect();
+        assert_eq!(all.len(), 26);
+        assert!(all.is_superset(&faces));
+        assert!(!all.contains(&a));
+
+        let corner: Vec3<usize> = Vec3::zero();
+        assert_eq!(corner.neighbours6().count(), 3);
+        assert_eq!(corner.neighbours26().count(), 7);
+    }
+
+    #[test]
+    fn parsing() {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(-?\d+),(-?\d+),(-?\d+)$").unwrap();
+        }
+        assert_eq!(Vec3::parse_with_regex("2,-1,5", &RE).unwrap(), Vec3::new(2, -1, 5));
+        assert!(Vec3::<isize>::parse_with_regex("2,1", &RE).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt::Debug;
+use std::ops::{Index, IndexMut};
+use anyhow::Context;
+use crate::utils::num::Int;
+
+/// A point or offset with `N` integer components, for puzzles in more dimensions than `Vec2` and `Vec3` cover.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VecN<T, const N: usize>(pub [T; N]);
+
+impl<T: Debug, const N: usize> Debug for VecN<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "V(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T: Int, const N: usize> Default for VecN<T, N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+vector_ops!([T, const N: usize] VecN<T, N>);
+
+impl<T, const N: usize> Index<usize> for VecN<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for VecN<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T: Int, const N: usize> VecN<T, N> {
+    pub const fn new(components: [T; N]) -> Self {
+        Self(components)
+    }
+
+    pub fn zero() -> Self {
+        Self([T::ZERO; N])
+    }
+
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Self(self.0.map(f))
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        let mut out = self;
+        for (a, b) in out.0.iter_mut().zip(other.0) {
+            *a = f(*a, b);
+        }
+        out
+    }
+
+    /// Parses the components from the first `N` capture groups of `regex`.
+    pub fn parse_with_regex(input: &str, regex: &regex::Regex) -> anyhow::Result<Self> {
+        let captures = regex.captures(input)
+            .with_context(|| format!("`{}` does not match {}", input, regex))?;
+        let mut out = Self::zero();
+        for (axis, c) in out.0.iter_mut().enumerate() {
+            let group = captures.get(axis + 1).with_context(|| format!("Missing coordinate {}", axis + 1))?;
+            *c = group.as_str().parse()?;
+        }
+        Ok(out)
+    }
+
+    pub fn manhattan_length(&self) -> T {
+        self.0.iter().fold(T::ZERO, |sum, c| sum + c.abs())
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        self.0.iter().zip(other.0).fold(T::ZERO, |sum, (a, b)| sum + a.abs_diff(b))
+    }
+
+    /// The largest distance along one axis, i.e. the number of steps when diagonals count as one.
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        self.0.iter().zip(other.0).fold(T::ZERO, |max, (a, b)| max.max(a.abs_diff(b)))
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.0.iter().zip(other.0).fold(T::ZERO, |sum, (a, b)| sum + *a * b)
+    }
+
+    pub fn signum(&self) -> Self {
+        self.map(Int::signum)
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(Int::abs)
+    }
+
+    /// The `2 * N` points one step away along a single axis, leaving out those `T` cannot hold.
+    pub fn axis_neighbours(&self) -> impl Iterator<Item = Self> {
+        let me = *self;
+        (0..N).flat_map(move |axis| {
+            [me[axis].checked_sub(T::ONE), me[axis].checked_add(T::ONE)].into_iter().flatten().map(move |c| {
+                let mut next = me;
+                next[axis] = c;
+                next
+            })
+        })
+    }
+
+    /// The `3^N - 1` points that differ by at most one in every component, leaving out those `T` cannot hold.
+    pub fn neighbours(&self) -> impl Iterator<Item = Self> {
+        let me = *self;
+        let count = 3usize.pow(N as u32);
+        // Each index is an offset with one base-3 digit per axis: 0 is one less, 1 the same and 2 one more
+        (0..count).filter(move |&i| i != count / 2).filter_map(move |i| {
+            let mut next = me;
+            let mut digits = i;
+            for c in next.0.iter_mut() {
+                *c = match digits % 3 {
+                    0 => c.checked_sub(T::ONE)?,
+                    1 => *c,
+                    _ => c.checked_add(T::ONE)?,
+                };
+                digits /= 3;
+            }
+            Some(next)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lazy_static::lazy_static;
+    use regex::Regex;
+    use super::VecN;
+
+    #[test]
+    fn arithmetic() {
+        let a = VecN([1, 2, 3, 4]);
+        let b = VecN([4, 3, 2, 1]);
+        assert_eq!(a + b, VecN([5; 4]));
+        assert_eq!(a - b, VecN([-3, -1, 1, 3]));
+        assert_eq!(-a, VecN([-1, -2, -3, -4]));
+        assert_eq!(a * 2, VecN([2, 4, 6, 8]));
+        assert_eq!(b / 2, VecN([2, 1, 1, 0]));
+        assert_eq!((a - b) % VecN([2; 4]), VecN([1, 1, 1, 1]));
+        assert_eq!(a.dot(&b), 20);
+        assert_eq!((a - b).signum(), VecN([-1, -1, 1, 1]));
+        assert_eq!((a - b).abs(), VecN([3, 1, 1, 3]));
+
+        let mut c = a;
+        c += b;
+        c -= &b;
+        c *= 3;
+        c /= 3;
+        assert_eq!(c, a);
+        assert_eq!(c[3], 4);
+    }
+
+    #[test]
+    fn distances() {
+        let a: VecN<i32, 3> = VecN([1, -2, 5]);
+        let b = VecN([4, 2, 5]);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.manhattan_length(), 8);
+    }
+
+    #[test]
+    fn neighbours() {
+        let origin: VecN<i64, 4> = VecN::zero();
+        assert_eq!(origin.axis_neighbours().count(), 8);
+        assert_eq!(origin.neighbours().count(), 80);
+        assert!(origin.neighbours().all(|n| n.chebyshev_distance(&origin) == 1));
+
+        // Nothing below zero for unsigned components
+        let corner: VecN<u8, 2> = VecN([0, 5]);
+        assert_eq!(corner.axis_neighbours().collect::<Vec<_>>(), vec![VecN([1, 5]), VecN([0, 4]), VecN([0, 6])]);
+        assert_eq!(corner.neighbours().count(), 5);
+    }
+
+    #[test]
+    fn parsing() {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"<x=(-?\d+), y=(-?\d+), z=(-?\d+), w=(-?\d+)>").unwrap();
+        }
+        let v: VecN<i32, 4> = VecN::parse_with_regex("<x=-1, y=0, z=2, w=30>", &RE).unwrap();
+        assert_eq!(v, VecN([-1, 0, 2, 30]));
+        assert!(VecN::<i32, 4>::parse_with_regex("<x=1, y=2>", &RE).is_err());
+        assert!(VecN::<u8, 4>::parse_with_regex("<x=-1, y=0, z=2, w=30>", &RE).is_err());
+    }
+}
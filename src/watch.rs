@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use anyhow::Context;
+use crate::answers::{parse_answer, quote, DayAnswers};
+use crate::input::InputSource;
+use crate::runner::{print_parse_error, DayReport, PartReport, ANSI_GREEN_PLUS, ANSI_RED_BANG};
+
+pub static ANSI_YELLOW_TILDE: &str = "[\x1b[33m~\x1b[0m]";
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// Carries the last answers over when a rebuilt binary replaces the running one
+const PREVIOUS_ANSWERS_VAR: &str = "AOC_WATCH_PREVIOUS";
+
+/// The inputs of `day` below `root`: `<day>.txt` and `<day>_<variant>.txt` in `inputs/<year>/` and `inputs/`.
+pub fn input_files(root: &Path, year: u16, day: u8) -> Vec<PathBuf> {
+    let plain = format!("{}.txt", day);
+    let variant = format!("{}_", day);
+    let mut files = Vec::new();
+    for dir in [root.join("inputs").join(year.to_string()), root.join("inputs")] {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == plain || (name.starts_with(&variant) && name.ends_with(".txt")) {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// The files to watch for `source`: the inputs of the day and, for `--input`, the file actually being solved.
+pub fn watched_files(root: &Path, year: u16, day: u8, source: &InputSource) -> Vec<PathBuf> {
+    let mut files = input_files(root, year, day);
+    if let InputSource::Path(path) = source {
+        if !files.contains(path) {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+/// The modification times of `paths`, to compare between polls. Missing files are left out.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    paths
+        .iter()
+        .filter_map(|path| Some((path.clone(), std::fs::metadata(path).ok()?.modified().ok()?)))
+        .collect()
+}
+
+/// Describes how the answer of part `n` compares to the last answer that part gave.
+pub fn describe_part(n: u8, previous: Option<&str>, report: &PartReport) -> String {
+    match (&report.answer, previous) {
+        (Ok(answer), None) => format!("{} Part {}: {} ({:.2?})", ANSI_GREEN_PLUS, n, answer, report.time),
+        (Ok(answer), Some(previous)) if answer == previous => {
+            format!("{} Part {}: {} (unchanged, {:.2?})", ANSI_GREEN_PLUS, n, answer, report.time)
+        }
+        (Ok(answer), Some(previous)) => {
+            format!("{} Part {}: {} (was {}, {:.2?})", ANSI_YELLOW_TILDE, n, answer, previous, report.time)
+        }
+        (Err(error), None) => format!("{} Part {} failed: {:#}", ANSI_RED_BANG, n, error),
+        (Err(error), Some(previous)) => {
+            format!("{} Part {} failed: {:#} (last answer {})", ANSI_RED_BANG, n, error, previous)
+        }
+    }
+}
+
+/// Prints `report` against the `previous` answers and remembers the new ones. Parts that failed keep their old answer.
+fn show(day: u8, report: &DayReport, previous: &mut DayAnswers) {
+    if let Some(error) = &report.parse_error {
+        print_parse_error(day, error);
+    }
+    for (n, part, last) in [(1, &report.part1, &mut previous.part1), (2, &report.part2, &mut previous.part2)] {
+        let Some(part) = part else { continue };
+        println!("{}", describe_part(n, last.as_deref(), part));
+        if let Ok(answer) = &part.answer {
+            *last = Some(answer.clone());
+        }
+    }
+}
+
+fn encode_answers(answers: &DayAnswers) -> String {
+    let mut out = String::new();
+    for (key, answer) in [("part1", &answers.part1), ("part2", &answers.part2)] {
+        if let Some(answer) = answer {
+            out += &format!("{} = {}\n", key, quote(answer));
+        }
+    }
+    out
+}
+
+fn decode_answers(encoded: &str) -> DayAnswers {
+    let mut answers = DayAnswers::default();
+    for line in encoded.lines() {
+        // Only ever written by an earlier run of this binary, a broken line just means nothing to compare against
+        let _ = parse_answer(line, &mut answers);
+    }
+    answers
+}
+
+/// Replaces this process with a fresh start of the rebuilt binary, passing the last answers on.
+#[cfg(unix)]
+fn restart(exe: &Path, previous: &DayAnswers) -> anyhow::Error {
+    use std::os::unix::process::CommandExt;
+    let error = std::process::Command::new(exe)
+        .args(std::env::args_os().skip(1))
+        .env(PREVIOUS_ANSWERS_VAR, encode_answers(previous))
+        .exec();
+    anyhow::Error::from(error).context(format!("Could not restart {}", exe.display()))
+}
+
+#[cfg(not(unix))]
+fn restart(exe: &Path, _previous: &DayAnswers) -> anyhow::Error {
+    anyhow::anyhow!("{} was rebuilt, start it again to pick up the changes", exe.display())
+}
+
+/// Runs `solve` for `day` every time one of its input files or the `source` file changes, until interrupted. When the binary itself is
+/// rebuilt it restarts into the new one. Each run clears the screen and shows how the answers changed.
+pub fn watch(
+    year: u16,
+    day: u8,
+    source: &InputSource,
+    mut solve: impl FnMut() -> anyhow::Result<DayReport>,
+) -> anyhow::Result<()> {
+    let exe = std::env::current_exe().context("Could not find the running binary")?;
+    let exe_time = || snapshot(std::slice::from_ref(&exe));
+    let mut previous = std::env::var(PREVIOUS_ANSWERS_VAR).map_or_else(|_| DayAnswers::default(), |v| decode_answers(&v));
+    let built = exe_time();
+
+    for run in 1.. {
+        let inputs = snapshot(&watched_files(Path::new("."), year, day, source));
+        print!("{}", CLEAR_SCREEN);
+        println!("[-] Day {} of {}, run {} (watching {} input files, Ctrl-C to stop)", day, year, run, inputs.len());
+        match solve() {
+            Ok(report) => show(day, &report, &mut previous),
+            Err(error) => println!("{} {:#}", ANSI_RED_BANG, error),
+        }
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let now = exe_time();
+            if now != built {
+                // Wait for the linker to finish writing it
+                std::thread::sleep(POLL_INTERVAL);
+                if exe_time() == now {
+                    return Err(restart(&exe, &previous));
+                }
+            }
+            if snapshot(&watched_files(Path::new("."), year, day, source)) != inputs {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(answer: anyhow::Result<&str>) -> PartReport {
+        PartReport { answer: answer.map(str::to_string), time: Duration::from_millis(1), alloc: None }
+    }
+
+    #[test]
+    fn describes_changes() {
+        assert_eq!(describe_part(1, None, &part(Ok("11"))), format!("{} Part 1: 11 (1.00ms)", ANSI_GREEN_PLUS));
+        assert!(describe_part(1, Some("11"), &part(Ok("11"))).contains("11 (unchanged, 1.00ms)"));
+        let changed = describe_part(2, Some("11"), &part(Ok("12")));
+        assert!(changed.starts_with(ANSI_YELLOW_TILDE) && changed.contains("12 (was 11, 1.00ms)"), "{}", changed);
+        assert!(describe_part(2, Some("11"), &part(Err(anyhow::anyhow!("boom")))).contains("boom (last answer 11)"));
+    }
+
+    #[test]
+    fn failed_parts_keep_their_answer() {
+        let report = DayReport {
+            year: 2024,
+            day: 1,
+            parse_time: Duration::ZERO,
+            parse_alloc: None,
+            parse_error: None,
+            part1: Some(part(Ok("7"))),
+            part2: Some(part(Err(anyhow::anyhow!("boom")))),
+        };
+        let mut previous = DayAnswers { part1: Some("6".into()), part2: Some("9".into()) };
+        show(1, &report, &mut previous);
+        assert_eq!(previous, DayAnswers { part1: Some("7".into()), part2: Some("9".into()) });
+        assert_eq!(decode_answers(&encode_answers(&previous)), previous);
+    }
+
+    #[test]
+    fn finds_inputs() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("inputs/2024")).unwrap();
+        for file in ["inputs/1.txt", "inputs/1_test1.txt", "inputs/11.txt", "inputs/2024/1_b.txt", "inputs/1_a.expected"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let files = input_files(&root, 2024, 1);
+        let names: Vec<_> = files.iter().map(|f| f.strip_prefix(&root).unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["inputs/1.txt", "inputs/1_test1.txt", "inputs/2024/1_b.txt"]);
+        assert_eq!(snapshot(&files).len(), 3);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn watches_explicit_input() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-explicit-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("inputs")).unwrap();
+        std::fs::write(root.join("inputs/1.txt"), "").unwrap();
+        let other = root.join("other.txt");
+
+        let files = watched_files(&root, 2024, 1, &InputSource::Path(other.clone()));
+        assert_eq!(files, vec![root.join("inputs/1.txt"), other]);
+        assert_eq!(watched_files(&root, 2024, 1, &InputSource::Default(2024, 1)), vec![root.join("inputs/1.txt")]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
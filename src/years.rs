@@ -0,0 +1 @@
+pub mod y2024;
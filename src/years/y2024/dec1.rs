@@ -1,3 +1,6 @@
+use anyhow::Context;
+use crate::input::parse_lines;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 pub struct HistorianHysteria {
@@ -6,22 +9,21 @@ pub struct HistorianHysteria {
 }
 
 impl AocDay for HistorianHysteria {
-    fn new(content: String) -> Self {
-        let mut left = Vec::new();
-        let mut right = Vec::new();
-        for line in content.lines() {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        let (left, right) = parse_lines(&content, |_, line| {
             let mut parts = line.split("   ");
-            left.push(parts.next().unwrap().parse().unwrap());
-            right.push(parts.next().unwrap().parse().unwrap());
-        }
+            let left: i32 = parts.next().context("Missing left list")?.parse()?;
+            let right: i32 = parts.next().context("Missing right list")?.parse()?;
+            Ok((left, right))
+        })?.into_iter().unzip();
 
-        HistorianHysteria {
+        Ok(HistorianHysteria {
             left,
             right,
-        }
+        })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut left_sorted = self.left.clone();
         let mut right_sorted = self.right.clone();
         left_sorted.sort();
@@ -32,10 +34,10 @@ impl AocDay for HistorianHysteria {
             .map(|(l, r)| (l-r).abs())
             .map(|x| x)
             .sum::<i32>();
-        dist.to_string()
+        Ok(dist.to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let sum = self.left
             .iter()
             .map(|l| {
@@ -43,6 +45,6 @@ impl AocDay for HistorianHysteria {
                 l * right_count as i32
             })
             .sum::<i32>();
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
\ No newline at end of file
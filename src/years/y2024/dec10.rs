@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use anyhow::Context;
+use crate::params::Params;
+use crate::runner::AocDay;
+use crate::utils::grid::Grid;
+use crate::utils::Vec2;
+
+pub struct HoofIt {
+    map: Grid<u32>,
+}
+
+impl HoofIt {
+    fn find_trails(&self, pos: Vec2, n: u32) -> HashSet<Vec2> {
+        let tile = self.map[pos];
+        if tile != n {
+            return HashSet::new();
+        }
+
+        if tile == 9 && n == 9 {
+            let mut set = HashSet::new();
+            set.insert(pos);
+            return set;
+        }
+
+        let mut sum = HashSet::new();
+        for next in self.map.neighbours4(pos) {
+            sum.extend(self.find_trails(next, n + 1));
+        }
+
+        sum
+    }
+
+    fn find_trails_unique(&self, pos: Vec2, n: u32) -> usize {
+        let tile = self.map[pos];
+        if tile != n {
+            return 0;
+        }
+
+        if tile == 9 && n == 9 {
+            return 1;
+        }
+
+        self.map.neighbours4(pos).map(|next| self.find_trails_unique(next, n + 1)).sum()
+    }
+}
+
+impl AocDay for HoofIt {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        // map is a grid of numbers
+        let map = Grid::parse(&content, |c| c.to_digit(10).with_context(|| format!("Invalid height '{}'", c)))?;
+        Ok(Self { map })
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        let sum: usize = self.map.positions(|&tile| tile == 0).map(|pos| self.find_trails(pos, 0).len()).sum();
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        let sum: usize = self.map.positions(|&tile| tile == 0).map(|pos| self.find_trails_unique(pos, 0)).sum();
+        Ok(sum.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_test() {
+        let content = "0123\n1234\n8765\n9876".to_string();
+        let day = HoofIt::new(content, &Params::default()).unwrap();
+        let trails = day.find_trails(Vec2::zero(), 0);
+        assert_eq!(trails.len(), 1);
+    }
+}
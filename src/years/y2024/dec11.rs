@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
+use anyhow::Context;
+use crate::params::{Param, Params};
 use crate::runner::AocDay;
 
 type Int = i128;
 
 pub struct PlutonianPebbles{
-    pebbles: Vec<Int>
+    pebbles: Vec<Int>,
+    blinks: usize,
+    blinks2: usize,
 }
 
 fn count_stones_emerging(cache: &mut HashMap<(Int, usize), Int>, pebble: Int, depth: usize) -> Int {
@@ -41,28 +45,35 @@ fn count_stones_emerging(cache: &mut HashMap<(Int, usize), Int>, pebble: Int, de
 }
 
 impl AocDay for PlutonianPebbles {
-    fn new(content: String) -> Self {
-        Self {
-            pebbles: content.split(" ").filter(|x| !x.is_empty()).map(|x| x.parse().unwrap()).collect()
-        }
+    const PARAMS: &'static [Param] = &[
+        Param { name: "blinks", default: "25", help: "Number of blinks in part 1" },
+        Param { name: "blinks2", default: "75", help: "Number of blinks in part 2" },
+    ];
+
+    fn new(content: String, params: &Params) -> anyhow::Result<Self> {
+        let pebbles = content.trim_end().split(" ")
+            .filter(|x| !x.is_empty())
+            .map(|x| x.parse().with_context(|| format!("Invalid pebble `{}`", x)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { pebbles, blinks: params.get("blinks")?, blinks2: params.get("blinks2")? })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut cache = HashMap::new();
         let mut sum: Int = 0;
         for pebble in &self.pebbles {
-            sum += count_stones_emerging(&mut cache, *pebble, 25);
+            sum += count_stones_emerging(&mut cache, *pebble, self.blinks);
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let mut cache = HashMap::new();
         let mut sum: Int = 0;
         for pebble in &self.pebbles {
-            sum += count_stones_emerging(&mut cache, *pebble, 75);
+            sum += count_stones_emerging(&mut cache, *pebble, self.blinks2);
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
 
@@ -70,7 +81,7 @@ impl AocDay for PlutonianPebbles {
 mod tests {
     use std::collections::HashMap;
 
-    use crate::days::dec11::count_stones_emerging;
+    use crate::years::y2024::dec11::count_stones_emerging;
 
     #[test]
     fn test_count_rule1() {
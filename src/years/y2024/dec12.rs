@@ -0,0 +1,199 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign};
+use crate::params::Params;
+use crate::runner::AocDay;
+use crate::utils::direction::Direction;
+use crate::utils::grid::Grid;
+use crate::utils::Vec2;
+
+#[derive(Clone)]
+pub struct GroupInfo {
+    pub perimeter: usize,
+    pub perimeter_elems: Vec<(Vec2, Direction)>,
+    pub area: usize,
+    pub char: char,
+}
+
+impl Debug for GroupInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(format!("Group[{}]", self.char).as_str())
+            .field("p", &self.perimeter)
+            .field("a", &self.area)
+            .finish()
+    }
+}
+
+impl GroupInfo {
+    fn is_zero(&self) -> bool {
+        self.perimeter == 0 && self.area == 0
+    }
+
+    fn price(&self) -> usize {
+        self.area * self.perimeter
+    }
+
+    fn price_walked(&self) -> usize {
+        self.area * self.unique_lines()
+    }
+
+    fn filter_neighbours(elems: &mut Vec<(Vec2, Direction)>, base: &(Vec2, Direction)) {
+        elems.retain(|elem| elem != base);
+
+        // Edges facing up or down run along x, the others along y
+        let along = if base.1 == Direction::Up || base.1 == Direction::Down { Vec2::new(1, 0) } else { Vec2::new(0, 1) };
+        for neighbour in [(base.0 - along, base.1), (base.0 + along, base.1)] {
+            if elems.contains(&neighbour) {
+                Self::filter_neighbours(elems, &neighbour);
+            }
+        }
+    }
+
+    fn unique_lines(&self) -> usize {
+        let mut elems = self.perimeter_elems.clone();
+        let mut count = 0;
+        while let Some(&elem) = elems.first() {
+            Self::filter_neighbours(&mut elems, &elem);
+            count += 1;
+        }
+
+        count
+    }
+}
+
+impl Add for GroupInfo {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        GroupInfo {
+            perimeter: self.perimeter + rhs.perimeter,
+            perimeter_elems: [self.perimeter_elems, rhs.perimeter_elems].concat(),
+            area: self.area + rhs.area,
+            char: self.char,
+        }
+    }
+}
+
+impl AddAssign for GroupInfo {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+#[derive(Clone)]
+pub struct GardenGroups(Grid<char>);
+
+impl GardenGroups {
+    fn segment_at(&mut self, pos: Vec2, c: char, dir: Direction) -> GroupInfo {
+        let tile = self.0[pos];
+
+        if tile != c {
+            let is_perimeter = tile.to_ascii_uppercase() != c;
+            if is_perimeter {
+                return GroupInfo {
+                    perimeter: 1,
+                    perimeter_elems: vec![(pos - dir.to_point(), dir)],
+                    area: 0,
+                    char: c,
+                };
+            }
+            return GroupInfo {
+                perimeter: 0,
+                perimeter_elems: vec![],
+                area: 0,
+                char: c,
+            };
+        }
+
+        self.0[pos] = tile.to_ascii_lowercase();
+
+        let mut group = GroupInfo {
+            perimeter: 0,
+            perimeter_elems: vec![],
+            area: 1,
+            char: c,
+        };
+
+        for dir in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            let next = pos + dir.to_point();
+            if self.0.contains(next) {
+                group += self.segment_at(next, c, dir);
+            } else {
+                group.perimeter += 1;
+                group.perimeter_elems.push((pos, dir));
+            }
+        }
+
+        group
+    }
+
+    fn segment(&self) -> Vec<GroupInfo> {
+        let mut groups = Vec::new();
+        let mut me = self.clone();
+
+        for pos in me.0.points() {
+            let tile = me.0[pos];
+
+            if tile.is_lowercase() {
+                continue;
+            }
+
+            let segment = me.segment_at(pos, tile, Direction::Up);
+            if !segment.is_zero() {
+                groups.push(segment);
+            }
+        }
+
+        groups
+    }
+}
+
+impl AocDay for GardenGroups {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(GardenGroups(Grid::parse(&content, Ok)?))
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        let groups = self.segment();
+        let area = groups.iter().map(|g| g.price()).sum::<usize>();
+        Ok(area.to_string())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        let groups = self.segment();
+        let area = groups.iter().map(|g| g.price_walked()).sum::<usize>();
+        Ok(area.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        let content = "AAA\nABA\nBBA".to_string();
+        let gg = GardenGroups::new(content, &Params::default()).unwrap();
+        let mut segments = gg.segment();
+        let b_seg = segments.pop().unwrap();
+        let a_seg = segments.pop().unwrap();
+
+        assert_eq!(a_seg.char, 'A');
+        assert_eq!(b_seg.char, 'B');
+        assert_eq!(a_seg.area, 6);
+        assert_eq!(b_seg.area, 3);
+        assert_eq!(a_seg.perimeter, 14);
+        assert_eq!(a_seg.perimeter_elems.len(), 14);
+        assert_eq!(b_seg.perimeter, 8);
+        assert_eq!(b_seg.perimeter_elems.len(), 8);
+    }
+
+    #[test]
+    fn e() {
+        let content = include_str!("../../../inputs/12_test2_e.txt").to_string();
+        let gg = GardenGroups::new(content, &Params::default()).unwrap();
+        let segments = gg.segment();
+        let e_seg = &segments[0];
+
+        assert_eq!(e_seg.area, 17);
+        assert_eq!(e_seg.unique_lines(), 12);
+    }
+}
\ No newline at end of file
@@ -1,36 +1,19 @@
+use crate::log::Progress;
+use crate::params::{Param, Params};
 use crate::runner::AocDay;
+use anyhow::Context;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::cmp::{Ordering, PartialOrd};
 use std::collections::HashMap;
-use std::ops::Deref;
 use z3::ast::{Ast, Int};
 use z3::SatResult;
+use crate::utils::Vec2;
 
 lazy_static! {
     static ref BTN_EXTRACTOR: Regex = Regex::new(r"Button .: X\+(\d+), Y\+(\d+)").unwrap();
     static ref PRIZE_EXTRACTOR: Regex = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
 }
 
-#[derive(Hash, Copy, Clone)]
-struct Vec2 {
-    x: isize,
-    y: isize,
-}
-
-impl<T> From<(&str, &T)> for Vec2
-where
-    T: Deref<Target=Regex>,
-{
-    fn from(value: (&str, &T)) -> Self {
-        let captures = value.1.captures(value.0).unwrap();
-        Vec2 {
-            x: captures.get(1).unwrap().as_str().parse().unwrap(),
-            y: captures.get(2).unwrap().as_str().parse().unwrap(),
-        }
-    }
-}
-
 #[derive(Clone)]
 struct ClawMachine {
     a_dir: Vec2,
@@ -38,32 +21,6 @@ struct ClawMachine {
     target: Vec2,
 }
 
-impl PartialEq<Self> for Vec2 {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-impl PartialOrd for Vec2 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.x == other.x && self.y == other.y {
-            Some(Ordering::Equal)
-        } else if self.x < other.x && self.y < other.y {
-            Some(Ordering::Less)
-        } else {
-            Some(Ordering::Greater)
-        }
-    }
-}
-
-impl Eq for Vec2 {}
-
-impl Ord for Vec2 {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
 fn solve_linear_equations(a1: i64, b1: i64, c1: i64, a2: i64, b2: i64, c2: i64) -> Option<(i64, i64)> {
     let cfg = z3::Config::new();
     let ctx = z3::Context::new(&cfg);
@@ -94,7 +51,7 @@ fn solve_linear_equations(a1: i64, b1: i64, c1: i64, a2: i64, b2: i64, c2: i64)
     let x_val = model.eval(&x, true).unwrap().as_i64().unwrap();
     let y_val = model.eval(&y, true).unwrap().as_i64().unwrap();
 
-    println!("Model: x={}, y={}", x_val, y_val);
+    crate::debug!("Model: x={}, y={}", x_val, y_val);
 
     Some((x_val, y_val))
 }
@@ -103,7 +60,7 @@ impl ClawMachine {
     #[allow(dead_code)]
     fn search_smallest_solution(&self) -> isize {
         let mut tokens_spent: isize = isize::MAX;
-        let pos = Vec2 { x: 0, y: 0 };
+        let pos = Vec2::zero();
         let mut queue = HashMap::new();
         queue.insert(pos, 0isize);
 
@@ -118,12 +75,9 @@ impl ClawMachine {
 
             // move with A
             {
-                let next = Vec2 {
-                    x: current.0.x + self.a_dir.x,
-                    y: current.0.y + self.a_dir.y,
-                };
+                let next = current.0 + self.a_dir;
 
-                if next < self.target {
+                if next.x < self.target.x && next.y < self.target.y {
                     let next_cost = current.1 + 3;
                     queue.insert(next, next_cost);
                 } else if next == self.target && ((current.1 + 3) < tokens_spent) {
@@ -133,12 +87,9 @@ impl ClawMachine {
 
             // move with B
             {
-                let next = Vec2 {
-                    x: current.0.x + self.b_dir.x,
-                    y: current.0.y + self.b_dir.y,
-                };
+                let next = current.0 + self.b_dir;
 
-                if next < self.target {
+                if next.x < self.target.x && next.y < self.target.y {
                     let next_cost = current.1 + 1;
                     queue.insert(next, next_cost);
                 } else if next == self.target && ((current.1 + 1) < tokens_spent) {
@@ -164,64 +115,72 @@ impl ClawMachine {
 
 pub struct ClawContraption {
     machines: Vec<ClawMachine>,
+    offset: isize,
 }
 
 impl AocDay for ClawContraption {
-    fn new(content: String) -> Self {
+    const PARAMS: &'static [Param] = &[
+        Param { name: "offset", default: "10000000000000", help: "Added to both prize coordinates in part 2" },
+    ];
+
+    fn new(content: String, params: &Params) -> anyhow::Result<Self> {
         let machines: Vec<ClawMachine> = content.split("\n\n")
-            .map(|machine_conf| {
-                let a_line = machine_conf.lines().nth(0).unwrap();
-                let b_line = machine_conf.lines().nth(1).unwrap();
-                let target_line = machine_conf.lines().nth(2).unwrap();
-
-                ClawMachine {
-                    a_dir: Vec2::from((a_line, &BTN_EXTRACTOR)),
-                    b_dir: Vec2::from((b_line, &BTN_EXTRACTOR)),
-                    target: Vec2::from((target_line, &PRIZE_EXTRACTOR)),
-                }
+            .enumerate()
+            .map(|(i, machine_conf)| {
+                let mut lines = machine_conf.lines();
+                let missing = || format!("Machine {} is missing a line", i + 1);
+                let a_line = lines.next().with_context(missing)?;
+                let b_line = lines.next().with_context(missing)?;
+                let target_line = lines.next().with_context(missing)?;
+
+                Ok(ClawMachine {
+                    a_dir: Vec2::parse_with_regex(a_line, &BTN_EXTRACTOR)?,
+                    b_dir: Vec2::parse_with_regex(b_line, &BTN_EXTRACTOR)?,
+                    target: Vec2::parse_with_regex(target_line, &PRIZE_EXTRACTOR)?,
+                })
             })
-            .collect();
-        ClawContraption { machines }
+            .collect::<anyhow::Result<_>>()?;
+        Ok(ClawContraption { machines, offset: params.get("offset")? })
     }
 
-    fn part1(&self) -> String {
-        let all_machines_len = self.machines.len();
-        self.machines.iter()
+    fn part1(&self) -> anyhow::Result<String> {
+        let mut progress = Progress::new("Solving machines", self.machines.len());
+        Ok(self.machines.iter()
             .enumerate()
             .map(|(i, machine)| {
-                println!("Solving machine {}/{}...", i + 1, all_machines_len);
+                progress.set(i + 1);
                 machine.search_smallest_solution_linalg()
             })
             .filter(|&solution| solution != isize::MAX)
             .sum::<isize>()
-            .to_string()
+            .to_string())
     }
 
-    fn part2(&self) -> String {
-        let all_machines_len = self.machines.len();
-        self.machines.clone().iter_mut()
+    fn part2(&self) -> anyhow::Result<String> {
+        let mut progress = Progress::new("Solving machines", self.machines.len());
+        Ok(self.machines.clone().iter_mut()
             .enumerate()
             .map(|(i, machine)| {
-                machine.target.x += 10000000000000;
-                machine.target.y += 10000000000000;
-                println!("Solving machine {}/{}...", i + 1, all_machines_len);
+                machine.target.x += self.offset;
+                machine.target.y += self.offset;
+                progress.set(i + 1);
                 machine.search_smallest_solution_linalg()
             })
             .filter(|&solution| solution != isize::MAX)
             .sum::<isize>()
-            .to_string()
+            .to_string())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::black_box;
+    use crate::bench::measure;
 
     #[test]
     fn parsing() {
         let content = "Button 1: X+1, Y+1\nButton 2: X+2, Y+2\nPrize: X=3, Y=3".to_string();
-        let contraption = ClawContraption::new(content);
+        let contraption = ClawContraption::new(content, &Params::default().with("offset", "0")).unwrap();
         assert_eq!(contraption.machines.len(), 1);
         let machine = &contraption.machines[0];
         assert_eq!(machine.a_dir.x, 1);
@@ -235,16 +194,18 @@ mod tests {
     #[test]
     fn unwinnable() {
         let content = "Button 1: X+20, Y+20\nButton 2: X+20, Y+20\nPrize: X=3, Y=3".to_string();
-        let contraption = ClawContraption::new(content);
+        let contraption = ClawContraption::new(content, &Params::default().with("offset", "0")).unwrap();
         let machine = &contraption.machines[0];
         assert_eq!(machine.search_smallest_solution_linalg(), isize::MAX);
     }
 
-    #[bench]
-    fn bench_smallest_search(b: &mut test::Bencher) {
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn bench_smallest_search() {
         let content = "Button 1: X+1, Y+1\nButton 2: X+2, Y+2\nPrize: X=3, Y=3".to_string();
-        let contraption = ClawContraption::new(content);
-        b.iter(|| black_box(contraption.machines[0].search_smallest_solution_linalg()));
+        let contraption = ClawContraption::new(content, &Params::default().with("offset", "0")).unwrap();
+        let stats = measure(10, 100, || (), |_| contraption.machines[0].search_smallest_solution_linalg());
+        println!("search_smallest_solution_linalg: {:?}", stats);
     }
 
     #[test]
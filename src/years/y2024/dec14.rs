@@ -1,16 +1,19 @@
+use std::io::IsTerminal;
+use anyhow::{bail, Context};
 use lazy_static::lazy_static;
+use crate::input::parse_lines;
+use crate::params::{Param, Params};
 use crate::runner::AocDay;
 use crate::utils::Vec2;
 
-
-#[cfg(not(test))]
-fn get_field() -> Vec2 {
-    Vec2::new(101, 103)
-}
-
-#[cfg(test)]
-fn get_field() -> Vec2 {
-    Vec2::new(11, 7)
+/// Parses a field size given as `<width>x<height>`, e.g. `101x103`.
+fn parse_field(value: &str) -> anyhow::Result<Vec2> {
+    let (width, height) = value.split_once('x').with_context(|| format!("Expected `<width>x<height>`, got `{}`", value))?;
+    let field = Vec2::new(width.trim().parse()?, height.trim().parse()?);
+    if field.x <= 0 || field.y <= 0 {
+        bail!("Field size must be positive, got `{}`", value);
+    }
+    Ok(field)
 }
 
 lazy_static! {
@@ -26,47 +29,58 @@ struct Robot {
 }
 
 impl Robot {
-    fn from_str(s: &str) -> Self {
-        Robot {
-            p: Vec2::parse_with_regex(s, &RE_P),
-            v: Vec2::parse_with_regex(s, &RE_V),
-            f: get_field(),
-        }
+    fn parse(s: &str, field: Vec2) -> anyhow::Result<Self> {
+        Ok(Robot {
+            p: Vec2::parse_with_regex(s, &RE_P)?,
+            v: Vec2::parse_with_regex(s, &RE_V)?,
+            f: field,
+        })
     }
+}
 
+impl Robot {
     fn step(&mut self, n: isize) {
         self.p = (self.p + &self.v * n) % self.f;
     }
 }
 
-pub struct RestroomRedoubt(Vec<Robot>);
+pub struct RestroomRedoubt {
+    robots: Vec<Robot>,
+    field: Vec2,
+}
 
 impl RestroomRedoubt {
-    fn display(robots: &[Robot]) {
-        let field = get_field();
+    fn display(&self, robots: &[Robot]) {
+        let field = self.field;
         let mut field = vec![vec![0; field.x as usize]; field.y as usize];
         for r in robots {
             field[r.p.y as usize][r.p.x as usize] += 1;
         }
         for row in field {
             for cell in row {
-                print!("{} ", if cell > 0 { cell.to_string() } else { ".".to_string() });
+                crate::out!("{} ", if cell > 0 { cell.to_string() } else { ".".to_string() });
             }
-            println!();
+            crate::outln!();
         }
     }
 }
 
 impl AocDay for RestroomRedoubt {
-    fn new(content: String) -> Self {
-        RestroomRedoubt(content.lines().map(Robot::from_str).collect())
+    const PARAMS: &'static [Param] = &[
+        Param { name: "field", default: "101x103", help: "Size of the area the robots move in, as <width>x<height>" },
+    ];
+
+    fn new(content: String, params: &Params) -> anyhow::Result<Self> {
+        let field = parse_field(params.get_str("field")?)?;
+        let robots = parse_lines(&content, |_, line| Robot::parse(line, field))?;
+        Ok(RestroomRedoubt { robots, field })
     }
 
-    fn part1(&self) -> String {
-        let field = get_field();
+    fn part1(&self) -> anyhow::Result<String> {
+        let field = self.field;
         let width = field.x;
         let height = field.y;
-        let mut robots = self.0.clone();
+        let mut robots = self.robots.clone();
         for r in &mut robots {
             r.step(100);
         }
@@ -75,15 +89,15 @@ impl AocDay for RestroomRedoubt {
         let lb = robots.iter().filter(|r| r.p.x != width as isize / 2 && r.p.y != height as isize / 2 && r.p.x < width as isize / 2 && r.p.y > height as isize / 2).count();
         let rb = robots.iter().filter(|r| r.p.x != width as isize / 2 && r.p.y != height as isize / 2 && r.p.x > width as isize / 2 && r.p.y > height as isize / 2).count();
 
-        (lt * rt * lb * rb).to_string()
+        Ok((lt * rt * lb * rb).to_string())
     }
 
-    #[cfg(not(test))]
-    fn part2(&self) -> String {
-        let field = get_field();
-        let width = field.x;
-        let height = field.y;
-        let mut robots = self.0.clone();
+    /// Steps through the robots' positions by hand until they form the tree, so it needs a terminal to read keys from.
+    fn part2(&self) -> anyhow::Result<String> {
+        if !std::io::stdin().is_terminal() {
+            bail!("Part 2 is interactive, run it from a terminal");
+        }
+        let mut robots = self.robots.clone();
         let mut step = 0isize;
         let mut steps = 0;
         loop {
@@ -93,19 +107,14 @@ impl AocDay for RestroomRedoubt {
 
             steps += step;
 
-            Self::display(&robots);
+            self.display(&robots);
             // read one character from stdin
-            println!("NEED FOR SPEED, LAST STEP: {}, CUR {}", step, steps);
+            crate::outln!("NEED FOR SPEED, LAST STEP: {}, CUR {}", step, steps);
             let ch = crate::utils::term::getch();
             let speed = crate::utils::term::ch_to_speed(ch);
             step = crate::utils::term::speed_scaling(speed);
         }
     }
-
-    #[cfg(test)]
-    fn part2(&self) -> String {
-        0.to_string()
-    }
 }
 
 #[cfg(test)]
@@ -115,15 +124,22 @@ mod tests {
     #[test]
     fn parsing() {
         let content = "p=2,4 v=2,-3".to_string();
-        let robot = Robot::from_str(&content);
+        let robot = Robot::parse(&content, Vec2::new(11, 7)).unwrap();
         assert_eq!(robot.p, Vec2::new(2, 4));
         assert_eq!(robot.v, Vec2::new(2, -3));
     }
 
+    #[test]
+    fn field() {
+        assert_eq!(parse_field("101x103").unwrap(), Vec2::new(101, 103));
+        assert!(parse_field("101").is_err());
+        assert!(parse_field("0x7").is_err());
+    }
+
     #[test]
     fn single() {
         let content = "p=2,4 v=2,-3".to_string();
-        let mut robot = Robot::from_str(&content);
+        let mut robot = Robot::parse(&content, Vec2::new(11, 7)).unwrap();
         robot.step(1);
         assert_eq!(robot.p, Vec2::new(4, 1));
         assert_eq!(robot.v, Vec2::new(2, -3));
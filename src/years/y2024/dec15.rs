@@ -1,18 +1,21 @@
 use std::fmt::Debug;
+use anyhow::Context;
+use crate::params::Params;
 use crate::runner::AocDay;
 use crate::utils::direction::Direction;
+use crate::utils::grid::Grid;
 use crate::utils::Vec2;
 
 #[derive(Clone)]
 pub struct WarehouseWoes {
-    pub map: Vec<Vec<char>>,
+    pub map: Grid<char>,
     pub directions: Vec<Direction>,
     pub pos: Vec2,
 }
 
 impl WarehouseWoes {
     fn get(&self, pos: Vec2) -> char {
-        self.map[pos.y as usize][pos.x as usize]
+        self.map[pos]
     }
 
     fn try_move(&mut self, pos: Vec2, dir: Direction) -> bool {
@@ -30,8 +33,8 @@ impl WarehouseWoes {
             }
 
             // move the box
-            self.map[new_pos.y as usize][new_pos.x as usize] = '.';
-            self.map[(new_pos.y + dir_vec.y) as usize][(new_pos.x + dir_vec.x) as usize] = 'O';
+            self.map[new_pos] = '.';
+            self.map[new_pos + dir_vec] = 'O';
             return true;
         }
 
@@ -47,22 +50,18 @@ impl WarehouseWoes {
     }
 
     fn widen(&mut self) {
-        let mut new_map = Vec::new();
-        for row in self.map.iter() {
-            let mut new_row = Vec::new();
-            for c in row.iter() {
-                if *c == 'O' {
-                    new_row.push('[');
-                    new_row.push(']');
-                } else {
-                    new_row.push(*c);
-                    new_row.push(*c);
-                }
+        let mut cells = Vec::new();
+        for c in self.map.rows().flatten() {
+            if *c == 'O' {
+                cells.push('[');
+                cells.push(']');
+            } else {
+                cells.push(*c);
+                cells.push(*c);
             }
-            new_map.push(new_row);
         }
 
-        self.map = new_map;
+        self.map = Grid::new(self.map.width() * 2, self.map.height(), cells);
         self.pos.x *= 2;
     }
 
@@ -84,10 +83,10 @@ impl WarehouseWoes {
             if do_move {
                 self.try_move_wide(new_pos + Vec2::new(1, 0), dir, true);
 
-                self.map[new_pos.y as usize][new_pos.x as usize] = '.';
-                self.map[(new_pos.y + dir_vec.y) as usize][(new_pos.x + dir_vec.x) as usize] = '[';
-                self.map[new_pos.y as usize][(new_pos.x + 1) as usize] = '.';
-                self.map[(new_pos.y + dir_vec.y) as usize][(new_pos.x + dir_vec.x + 1) as usize] = ']';
+                self.map[new_pos] = '.';
+                self.map[new_pos + dir_vec] = '[';
+                self.map[new_pos + Vec2::new(1, 0)] = '.';
+                self.map[new_pos + dir_vec + Vec2::new(1, 0)] = ']';
             }
             return true;
         }
@@ -102,10 +101,10 @@ impl WarehouseWoes {
             if do_move {
                 self.try_move_wide(new_pos + Vec2::new(-1, 0), dir, true);
 
-                self.map[new_pos.y as usize][new_pos.x as usize] = '.';
-                self.map[new_pos.y as usize][(new_pos.x - 1) as usize] = '.';
-                self.map[(new_pos.y + dir_vec.y) as usize][(new_pos.x + dir_vec.x) as usize] = ']';
-                self.map[(new_pos.y + dir_vec.y) as usize][(new_pos.x + dir_vec.x - 1) as usize] = '[';
+                self.map[new_pos] = '.';
+                self.map[new_pos + Vec2::new(-1, 0)] = '.';
+                self.map[new_pos + dir_vec] = ']';
+                self.map[new_pos + dir_vec + Vec2::new(-1, 0)] = '[';
             }
             return true;
         }
@@ -124,81 +123,44 @@ impl WarehouseWoes {
 
 impl Debug for WarehouseWoes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, c) in row.iter().enumerate() {
-                if Vec2::new(x as isize, y as isize) == self.pos {
-                    write!(f, "@")?;
-                    continue;
-                }
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        writeln!(f, "{}", self.map.render(|pos, &c| if pos == self.pos { '@' } else { c }))
     }
 }
 
 impl AocDay for WarehouseWoes {
-    fn new(content: String) -> Self {
-        let mut map_vec = Vec::new();
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
         let mut directions = Vec::new();
-        let mut pos = Vec2::new(0, 0);
-        let (map, line) = content.split_once("\n\n").unwrap();
-        for (y, line) in map.lines().enumerate() {
-            let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                if c == '@' {
-                    pos = Vec2::new(x as isize, y as isize);
-                    row.push('.');
-                } else {
-                    row.push(c);
-                }
-            }
-            map_vec.push(row);
-        }
+        let (map, line) = content.split_once("\n\n").context("Missing blank line between map and moves")?;
+        let (map, markers) = Grid::parse_with_markers(map, "@", |_, c| Ok(if c == '@' { '.' } else { c }))?;
         for line in line.lines() {
             for c in line.chars() {
-                directions.push(Direction::from_char(c));
+                directions.push(Direction::try_from(c).with_context(|| format!("Invalid move in `{}`", line))?);
             }
         }
-        Self { map: map_vec, directions, pos }
+        Ok(Self { map, directions, pos: markers.single('@')? })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut day = self.clone();
         while !day.directions.is_empty() {
             day.walk();
         }
 
-        let mut sum = 0;
-        for (y, row) in day.map.iter().enumerate() {
-            for (x, c) in row.iter().enumerate() {
-                if *c == 'O' {
-                    sum += y * 100 + x;
-                }
-            }
-        }
+        let sum: isize = day.map.positions(|&c| c == 'O').map(|pos| pos.y * 100 + pos.x).sum();
 
-        sum.to_string()
+        Ok(sum.to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let mut day = self.clone();
         // day.widen();
         while !day.directions.is_empty() {
             day.walk();
         }
 
-        let mut sum = 0;
-        for (y, row) in day.map.iter().enumerate() {
-            for (x, c) in row.iter().enumerate() {
-                if *c == '[' {
-                    sum += y * 100 + x;
-                }
-            }
-        }
+        let sum: isize = day.map.positions(|&c| c == '[').map(|pos| pos.y * 100 + pos.x).sum();
 
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
 
@@ -211,11 +173,11 @@ mod tests {
         let content = "#########\n\
                               #...O@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.walk();
         println!("{:?}", day);
-        assert_eq!(day.map[1][3], 'O');
+        assert_eq!(day.map[Vec2::new(3, 1)], 'O');
     }
 
     #[test]
@@ -223,12 +185,12 @@ mod tests {
         let content = "#########\n\
                               #..OO@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.walk();
         println!("{:?}", day);
-        assert_eq!(day.map[1][3], 'O');
-        assert_eq!(day.map[1][2], 'O');
+        assert_eq!(day.map[Vec2::new(3, 1)], 'O');
+        assert_eq!(day.map[Vec2::new(2, 1)], 'O');
     }
 
     #[test]
@@ -236,7 +198,7 @@ mod tests {
         let content = "#########\n\
                               #OOOO@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.walk();
         println!("{:?}", day);
@@ -248,7 +210,7 @@ mod tests {
         let content = "#########\n\
                               #....@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.walk();
         println!("{:?}", day);
@@ -260,16 +222,16 @@ mod tests {
         let content = "#########\n\
                               #...O@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.widen();
         println!("{:?}", day);
-        assert_eq!(day.map[1][8], '[');
-        assert_eq!(day.map[1][9], ']');
+        assert_eq!(day.map[Vec2::new(8, 1)], '[');
+        assert_eq!(day.map[Vec2::new(9, 1)], ']');
         day.walk_wide();
         println!("{:?}", day);
-        assert_eq!(day.map[1][7], '[');
-        assert_eq!(day.map[1][8], ']');
+        assert_eq!(day.map[Vec2::new(7, 1)], '[');
+        assert_eq!(day.map[Vec2::new(8, 1)], ']');
     }
 
     #[test]
@@ -277,16 +239,16 @@ mod tests {
         let content = "#########\n\
                               #OOOO@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.widen();
         println!("{:?}", day);
-        assert_eq!(day.map[1][8], '[');
-        assert_eq!(day.map[1][9], ']');
+        assert_eq!(day.map[Vec2::new(8, 1)], '[');
+        assert_eq!(day.map[Vec2::new(9, 1)], ']');
         day.walk_wide();
         println!("{:?}", day);
-        assert_eq!(day.map[1][8], '[');
-        assert_eq!(day.map[1][9], ']');
+        assert_eq!(day.map[Vec2::new(8, 1)], '[');
+        assert_eq!(day.map[Vec2::new(9, 1)], ']');
     }
 
     #[test]
@@ -294,16 +256,16 @@ mod tests {
         let content = "#########\n\
                               #..OO@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.widen();
         println!("{:?}", day);
-        assert_eq!(day.map[1][8], '[');
-        assert_eq!(day.map[1][9], ']');
+        assert_eq!(day.map[Vec2::new(8, 1)], '[');
+        assert_eq!(day.map[Vec2::new(9, 1)], ']');
         day.walk_wide();
         println!("{:?}", day);
-        assert_eq!(day.map[1][7], '[');
-        assert_eq!(day.map[1][8], ']');
+        assert_eq!(day.map[Vec2::new(7, 1)], '[');
+        assert_eq!(day.map[Vec2::new(8, 1)], ']');
     }
 
     #[test]
@@ -311,7 +273,7 @@ mod tests {
         let content = "#########\n\
                               #....@..#\n\
                               #########\n\n<".to_string();
-        let mut day = WarehouseWoes::new(content);
+        let mut day = WarehouseWoes::new(content, &Params::default()).unwrap();
         println!("{:?}", day);
         day.widen();
         println!("{:?}", day);
@@ -1,7 +1,8 @@
 use itertools::Itertools;
 use pathfinding::{directed::astar, prelude::{astar_bag, dijkstra}};
 
-use crate::{runner::AocDay, utils::{direction::Direction, Vec2}};
+use anyhow::bail;
+use crate::{cancel::CancelToken, log::{self, Level}, params::Params, runner::AocDay, utils::{direction::Direction, grid::Grid, Vec2}};
 
 #[derive(Clone, PartialEq)]
 enum Tile {
@@ -10,7 +11,7 @@ enum Tile {
 
 #[derive(Clone)]
 struct Maze {
-    maze: Vec<Vec<Tile>>,
+    maze: Grid<Tile>,
     start: Vec2,
     end: Vec2,
 }
@@ -20,7 +21,23 @@ type Node = (Vec2, Direction);
 #[allow(dead_code)]
 impl Maze {
     fn get(&self, pos: &Vec2) -> Tile {
-        self.maze[pos.y as usize][pos.x as usize].clone()
+        self.maze[*pos].clone()
+    }
+
+    /// Draws the maze with `X` at `pos` and `O` wherever `on_path` holds.
+    fn render(&self, pos: &Vec2, on_path: impl Fn(Vec2) -> bool) -> String {
+        self.maze.render(|at, tile| {
+            if at == *pos {
+                'X'
+            } else if on_path(at) {
+                'O'
+            } else {
+                match tile {
+                    Tile::Wall => '#',
+                    Tile::Space => '.',
+                }
+            }
+        })
     }
 
     fn dfs(&self, pos: &Vec2, dir: &Direction, visited: &[Vec2], steps: isize, rotations: isize, min_result: &mut isize) {
@@ -41,21 +58,9 @@ impl Maze {
         new_visited.push(*pos);
 
         if *pos == self.end {
-            println!("Found end in {} steps and {} rotations (Score={})", steps, rotations, steps + 1000 * rotations);
-            for (x, row) in self.maze.iter().enumerate() {
-                for (y, tile) in row.iter().enumerate() {
-                    if *pos == Vec2::new(y as isize, x as isize) {
-                        print!("X");
-                    } else if new_visited.contains(&Vec2::new(y as isize, x as isize)) {
-                        print!("O"); 
-                    } else {
-                        match tile {
-                            Tile::Wall => print!("#"),
-                            Tile::Space => print!("."),
-                        }
-                    }
-                }
-                println!();
+            crate::debug!("Found end in {} steps and {} rotations (Score={})", steps, rotations, steps + 1000 * rotations);
+            if log::enabled(Level::Trace) {
+                crate::trace!("{}", self.render(pos, |at| new_visited.contains(&at)));
             }
 
             if (steps + 1000 * rotations) < *min_result {
@@ -66,7 +71,7 @@ impl Maze {
 
         for new_dir in (*dir).iter_forwards_from() {
             let new_pos = pos + new_dir.to_point();
-            if new_pos.x < 0 || new_pos.y < 0 || new_pos.x >= self.maze[0].len() as isize || new_pos.y >= self.maze.len() as isize {
+            if !self.maze.contains(new_pos) {
                 continue;
             }
 
@@ -78,12 +83,17 @@ impl Maze {
         }
     }
 
-    fn search(&self, pos: &Vec2, dir: &Direction) -> isize {
+    fn search(&self, pos: &Vec2, dir: &Direction, cancel: &CancelToken) -> isize {
         let start = (pos.clone(), dir.clone());
 
         let result = dijkstra(
             &start,
             |node: &Node| {
+                // Running out of nodes ends the search, the caller reports the cancellation
+                if cancel.is_cancelled() {
+                    return Vec::new();
+                }
+
                 let orthogonal_moves = node.1.orthogonal();
             
                 let mut next_nodes = Vec::with_capacity(3);
@@ -106,20 +116,8 @@ impl Maze {
         );
 
         if let Some(result) = result {
-            for (x, row) in self.maze.iter().enumerate() {
-                for (y, tile) in row.iter().enumerate() {
-                    if *pos == Vec2::new(y as isize, x as isize) {
-                        print!("X");
-                    } else if result.0.iter().any(|v| v.0.x == (x as isize) && v.0.y == (y as isize)) {
-                        print!("O"); 
-                    } else {
-                        match tile {
-                            Tile::Wall => print!("#"),
-                            Tile::Space => print!("."),
-                        }
-                    }
-                }
-                println!();
+            if log::enabled(Level::Trace) {
+                crate::trace!("{}", self.render(pos, |at| result.0.iter().any(|v| v.0 == at)));
             }
             result.1
         } else {
@@ -127,12 +125,16 @@ impl Maze {
         }
     }
 
-    fn search_tiles(&self, pos: &Vec2, dir: &Direction) -> isize {
+    fn search_tiles(&self, pos: &Vec2, dir: &Direction, cancel: &CancelToken) -> isize {
         let start = (pos.clone(), dir.clone());
 
         let result = astar_bag(
             &start,
             |node: &Node| {
+                if cancel.is_cancelled() {
+                    return Vec::new();
+                }
+
                 let orthogonal_moves = node.1.orthogonal();
             
                 let mut next_nodes = Vec::with_capacity(3);
@@ -172,46 +174,34 @@ pub struct ReindeerMaze{
 }
 
 impl AocDay for ReindeerMaze {
-    fn new(content: String) -> Self {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
         // Parse the maze (E = End, S = Start, # = Wall, . = Path)
-        let mut maze = Vec::new();
-        let mut start = Vec2::new(0, 0);
-        let mut end = Vec2::new(0, 0);
-        for (y, line) in content.lines().enumerate() {
-            let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                let tile = match c {
-                    '#' => Tile::Wall,
-                    '.' => Tile::Space,
-                    'S' => {
-                        start = Vec2::new(x as isize, y as isize);
-                        Tile::Space
-                    },
-                    'E' => {
-                        end = Vec2::new(x as isize, y as isize);
-                        Tile::Space
-                    },
-                    _ => panic!("Invalid character in maze: {}", c),
-                };
-                row.push(tile);
-            }
-            maze.push(row);
-        }
+        let (maze, markers) = Grid::parse_with_markers(&content, "SE", |_, c| match c {
+            '#' => Ok(Tile::Wall),
+            '.' | 'S' | 'E' => Ok(Tile::Space),
+            _ => bail!("Invalid character in maze: {}", c),
+        })?;
 
-        Self {
+        Ok(Self {
             maze: Maze {
                 maze,
-                start,
-                end,
+                start: markers.single('S')?,
+                end: markers.single('E')?,
             }
-        }
+        })
     }
 
-    fn part1(&self) -> String {
-        self.maze.search(&self.maze.start, &Direction::Left).to_string()
+    fn part1(&self) -> anyhow::Result<String> {
+        let cancel = CancelToken::current();
+        let score = self.maze.search(&self.maze.start, &Direction::Left, &cancel);
+        cancel.check()?;
+        Ok(score.to_string())
     }
 
-    fn part2(&self) -> String {
-        self.maze.search_tiles(&self.maze.start, &Direction::Left).to_string()
+    fn part2(&self) -> anyhow::Result<String> {
+        let cancel = CancelToken::current();
+        let tiles = self.maze.search_tiles(&self.maze.start, &Direction::Left, &cancel);
+        cancel.check()?;
+        Ok(tiles.to_string())
     }
 }
\ No newline at end of file
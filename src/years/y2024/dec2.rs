@@ -1,12 +1,20 @@
+use anyhow::bail;
+use crate::input::parse_lines;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 #[derive(Debug)]
 pub struct ReportLine(Vec<i32>);
 
-impl From<String> for ReportLine {
-    fn from(value: String) -> Self {
-        let values: Vec<i32> = value.split(" ").map(|x| x.parse().unwrap()).collect();
-        ReportLine(values)
+impl TryFrom<&str> for ReportLine {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let values = value.split(" ").map(|x| x.parse()).collect::<Result<Vec<i32>, _>>()?;
+        if values.is_empty() {
+            bail!("Empty report");
+        }
+        Ok(ReportLine(values))
     }
 }
 
@@ -52,19 +60,19 @@ pub struct RedNosedReports {
 }
 
 impl AocDay for RedNosedReports {
-    fn new(content: String) -> Self {
-        RedNosedReports {
-            lines: content.lines().map(|x| ReportLine::from(x.to_string())).collect()
-        }
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(RedNosedReports {
+            lines: parse_lines(&content, |_, x| ReportLine::try_from(x))?
+        })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let all_increasing_or_decreasing = self.lines.iter().filter(|x| x.is_all_increasing() || x.is_all_decreasing()).count();
-        all_increasing_or_decreasing.to_string()
+        Ok(all_increasing_or_decreasing.to_string())
     }
 
-    fn part2(&self) -> String { 
+    fn part2(&self) -> anyhow::Result<String> { 
         let all_increasing_or_decreasing = self.lines.iter().filter(|x| x.is_same_direction_skip()).count();
-        all_increasing_or_decreasing.to_string()
+        Ok(all_increasing_or_decreasing.to_string())
     }
 }
\ No newline at end of file
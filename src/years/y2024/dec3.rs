@@ -1,3 +1,5 @@
+use anyhow::Context;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 #[derive(Debug)]
@@ -19,11 +21,11 @@ pub struct MullItOver {
 
 
 impl AocDay for MullItOver {
-    fn new(content: String) -> Self {
-        Self { content }
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(Self { content })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let regex = regex::Regex::new(r"mul\((\d+,\d+)\)").unwrap();
         let mut muls = vec![];
 
@@ -33,7 +35,8 @@ impl AocDay for MullItOver {
             let mut digits = mat.as_str();
             digits = digits.trim_start_matches("mul(");
             digits = digits.trim_end_matches(")");
-            let values: Vec<i32> = digits.split(",").map(|x| x.parse().unwrap()).collect();
+            let values = digits.split(",").map(|x| x.parse()).collect::<Result<Vec<i32>, _>>()
+                .with_context(|| format!("Invalid instruction `{}`", mat.as_str()))?;
             let mul = Mul::new(values);
             muls.push(mul);
         }
@@ -43,10 +46,10 @@ impl AocDay for MullItOver {
         for mul in muls {
             sum += mul.mul();
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 
-    fn part2(&self) -> String { 
+    fn part2(&self) -> anyhow::Result<String> { 
         let do_dont_regex = regex::Regex::new(r"do(n't)?\(\)").unwrap();
         let mut allowed_ranges = vec![];
         let mut is_do = true;
@@ -93,7 +96,8 @@ impl AocDay for MullItOver {
             let mut digits = mat.as_str();
             digits = digits.trim_start_matches("mul(");
             digits = digits.trim_end_matches(")");
-            let values: Vec<i32> = digits.split(",").map(|x| x.parse().unwrap()).collect();
+            let values = digits.split(",").map(|x| x.parse()).collect::<Result<Vec<i32>, _>>()
+                .with_context(|| format!("Invalid instruction `{}`", mat.as_str()))?;
             let mul = Mul::new(values);
             muls.push(mul);
         }
@@ -103,6 +107,6 @@ impl AocDay for MullItOver {
         for mul in muls {
             sum += mul.mul();
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::params::Params;
+use crate::runner::AocDay;
+use crate::utils::direction::Direction8;
+use crate::utils::grid::Grid;
+use crate::utils::Vec2;
+
+// cross-word puzzle
+pub struct CeresSearch(Grid<char>);
+
+impl CeresSearch {
+    fn recurse_find(&self, pos: Vec2, chars_left: &str, step: Vec2) -> i32 {
+        let Some(next) = chars_left.chars().next() else {
+            return 1;
+        };
+
+        if self.0.get(pos) != Some(&next) {
+            return 0;
+        }
+
+        self.recurse_find(pos + step, &chars_left[1..], step)
+    }
+
+    fn find(&self, chars: &str) -> i32 {
+        let mut n = 0;
+        for pos in self.0.points() {
+            for direction in Direction8::iter() {
+                n += self.recurse_find(pos, chars, direction.to_point());
+            }
+        }
+
+        n
+    }
+
+    fn find_mas_x(&self) -> i32 {
+        let mut n = 0;
+        for pos in self.0.positions(|&c| c == 'A') {
+            // Off the grid reads as nothing, so an `A` on the edge never matches
+            let at = |direction: Direction8| self.0.get(pos + direction.to_point()).copied();
+            let (tl, tr) = (at(Direction8::UpLeft), at(Direction8::UpRight));
+            let (bl, br) = (at(Direction8::DownLeft), at(Direction8::DownRight));
+
+            let from_tl_to_br = tl == Some('M') && br == Some('S');
+            let from_br_to_tl = tl == Some('S') && br == Some('M');
+
+            let frm_bl_to_tr = bl == Some('M') && tr == Some('S');
+            let frm_tr_to_bl = bl == Some('S') && tr == Some('M');
+
+            if (from_tl_to_br || from_br_to_tl) && (frm_bl_to_tr || frm_tr_to_bl) {
+                n += 1;
+            }
+        }
+
+        n
+    }
+}
+
+impl AocDay for CeresSearch {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(Self(Grid::parse(&content, Ok)?))
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        let all = self.find("XMAS");
+        Ok(all.to_string())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        let all = self.find_mas_x();
+        Ok(all.to_string())
+    }
+}
\ No newline at end of file
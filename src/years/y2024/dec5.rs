@@ -1,4 +1,7 @@
 use std::cmp::Ordering;
+use anyhow::Context;
+use crate::input::parse_lines;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 #[derive(Clone)]
@@ -12,13 +15,11 @@ trait Orders<T> {
 }
 
 impl TryFrom<&str> for PageRule {
-    type Error = ();
+    type Error = anyhow::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut parts = value.split('|');
-        let left = parts.next().ok_or(())?.parse().map_err(|_| ())?;
-        let right = parts.next().ok_or(())?.parse().map_err(|_| ())?;
-        Ok(Self { left, right })
+        let (left, right) = value.split_once('|').context("Expected a rule like `47|53`")?;
+        Ok(Self { left: left.parse()?, right: right.parse()? })
     }
 }
 
@@ -50,15 +51,11 @@ impl Orders<i32> for PageRule {
 struct PageUpdate(Vec<i32>);
 
 impl TryFrom<&str> for PageUpdate {
-    type Error = ();
+    type Error = anyhow::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parts = value.split(',').map(|x| x.parse().map_err(|_| ()));
-        let mut vec = Vec::new();
-        for part in parts {
-            vec.push(part?);
-        }
-        Ok(Self(vec))
+        let pages = value.split(',').map(|x| x.parse().with_context(|| format!("Invalid page `{}`", x)));
+        Ok(Self(pages.collect::<anyhow::Result<_>>()?))
     }
 }
 
@@ -100,42 +97,24 @@ pub struct PrintQueue {
 }
 
 impl AocDay for PrintQueue {
-    fn new(content: String) -> Self {
-        let mut lines = content.lines();
-        let mut rules = Vec::new();
-        let mut updates = Vec::new();
-        loop {
-            let line = lines.next();
-            if line.is_none() {
-                break;
-            }
-            if let Ok(rule) = PageRule::try_from(line.unwrap()) {
-                rules.push(rule);
-            } else {
-                break;
-            }
-        }
-
-        for line in lines {
-            if let Ok(update) = PageUpdate::try_from(line) {
-                updates.push(update);
-            }
-        }
-
-        Self { rules, updates }
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        let (rules, updates) = content.split_once("\n\n").context("Missing blank line between rules and updates")?;
+        let rules = parse_lines(rules, |_, line| PageRule::try_from(line)).context("In the ordering rules")?;
+        let updates = parse_lines(updates, |_, line| PageUpdate::try_from(line)).context("In the updates")?;
+        Ok(Self { rules, updates })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let sum: i32 = self.updates
             .iter()
             .filter(|update| self.rules.iter().all(|r| r.matches(update)))
             .map(|update| update.get_middle())
             .sum();
 
-        sum.to_string()
+        Ok(sum.to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let sum: i32 = self.updates
             .iter()
             .filter(|update| self.rules.iter().any(|r| !r.matches(update)))
@@ -143,7 +122,7 @@ impl AocDay for PrintQueue {
             .map(|update| update.get_middle())
             .sum();
 
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
 
@@ -168,4 +147,13 @@ mod tests {
         let update = PageUpdate::try_from("75,29,13").unwrap();
         assert_eq!(update.get_middle(), 29);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reports_bad_lines() {
+        let error = PrintQueue::new("47|53\n47-61\n\n75,47".to_string(), &Params::default()).err().unwrap();
+        assert!(format!("{:#}", error).contains("line 2: `47-61`"), "{:#}", error);
+        let error = PrintQueue::new("47|53\n\n75,47\n75,x".to_string(), &Params::default()).err().unwrap();
+        assert!(format!("{:#}", error).contains("`75,x`: Invalid page `x`"), "{:#}", error);
+        assert!(PrintQueue::new("47|53\n75,47".to_string(), &Params::default()).is_err());
+    }
+}
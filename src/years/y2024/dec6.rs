@@ -0,0 +1,211 @@
+use std::fmt::{Display, Formatter};
+use anyhow::bail;
+use crate::cancel::CancelToken;
+use crate::params::Params;
+use crate::runner::AocDay;
+use crate::utils::direction::Direction;
+use crate::utils::grid::Grid;
+use crate::utils::Vec2;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Tile {
+    Wall,
+    Open,
+    OutOfBounds,
+}
+
+#[derive(Clone)]
+struct Guard {
+    map: Grid<Tile>,
+    guard: Vec2,
+    direction: Direction,
+}
+
+impl TryFrom<&str> for Guard {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (map, markers) = Grid::parse_with_markers(value, "^", |_, c| match c {
+            '#' => Ok(Tile::Wall),
+            '.' | '^' => Ok(Tile::Open),
+            _ => bail!("Invalid character '{}' in map", c)
+        })?;
+        Ok(Guard {
+            map,
+            guard: markers.single('^')?,
+            direction: Direction::Up,
+        })
+    }
+}
+
+impl Guard {
+    fn tile_at(&self, pos: Vec2) -> Tile {
+        self.map.get(pos).copied().unwrap_or(Tile::OutOfBounds)
+    }
+
+    fn next_tile(&self) -> Tile {
+        self.tile_at(self.guard + self.direction.to_point())
+    }
+
+    pub fn set_wall_forwards(&mut self) {
+        let next_pos = self.guard + self.direction.to_point();
+        self.map[next_pos] = Tile::Wall;
+    }
+
+    fn step(&mut self) -> anyhow::Result<bool> {
+        let next_pos = self.guard + self.direction.to_point();
+        let tile = self.tile_at(next_pos);
+        if tile == Tile::Wall {
+            self.direction = self.direction.turn_right();
+            Ok(true)
+        } else if tile == Tile::Open {
+            self.guard = next_pos;
+            Ok(false)
+        } else {
+            bail!("OOB at {:?}", next_pos);
+        }
+    }
+
+    fn get_pos(&self) -> Vec2 {
+        self.guard
+    }
+}
+
+impl Display for Guard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let map = self.map.render(|pos, tile| {
+            if pos == self.guard {
+                return self.direction.to_arrow();
+            }
+            match tile {
+                Tile::Wall => '#',
+                Tile::Open => '.',
+                Tile::OutOfBounds => '-',
+            }
+        });
+        writeln!(f, "{}", map)
+    }
+}
+
+pub struct GuardGallivant(Guard);
+
+impl GuardGallivant {
+    fn check_loop(guard: &mut Guard, mut positions: Grid<Vec<Direction>>, cancel: &CancelToken) -> anyhow::Result<bool> {
+        if guard.next_tile() != Tile::Wall {
+            bail!("Next tile is not wall");
+        }
+
+        loop {
+            cancel.check()?;
+            let result = guard.step();
+            if result.is_err() {
+                return Ok(false);
+            }
+
+            let pos = guard.get_pos();
+            // Loop = we have been here before and we are facing the same direction
+            if positions[pos].contains(&guard.direction) {
+                return Ok(true);
+            }
+
+            positions[pos].push(guard.direction);
+        }
+    }
+}
+
+impl AocDay for GuardGallivant {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        let guard = Guard::try_from(content.as_str())?;
+        Ok(GuardGallivant(guard))
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        let mut guard_positions = Vec::new();
+        let mut guard = self.0.clone();
+        guard_positions.push(guard.get_pos());
+        let cancel = CancelToken::current();
+        loop {
+            cancel.check()?;
+            let result = guard.step();
+            if result.is_err() {
+                break;
+            }
+            if result.unwrap() {
+                crate::trace!("\n{}", guard);
+            }
+            let pos = guard.get_pos();
+            if !guard_positions.contains(&pos) {
+                guard_positions.push(pos);
+            }
+        }
+
+        Ok(guard_positions.len().to_string())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        let mut loops = 0;
+        let mut guard = self.0.clone();
+        let mut positions = Grid::filled(guard.map.width(), guard.map.height(), vec![]);
+        let start_pos = guard.get_pos();
+
+        let cancel = CancelToken::current();
+        loop {
+            cancel.check()?;
+            let position = guard.get_pos();
+            positions[position].push(guard.direction);
+            let next_tile = guard.next_tile();
+            match next_tile {
+                Tile::Wall => {
+                    guard.direction = guard.direction.turn_right();
+                }
+                Tile::Open => {
+                    if position != start_pos {
+                        let mut new_track_guard = guard.clone();
+                        new_track_guard.set_wall_forwards();
+                        if Self::check_loop(&mut new_track_guard, positions.clone(), &cancel)? {
+                            loops += 1;
+                        }
+                    }
+                    guard.step()?; // we know we are on open tile
+                }
+                Tile::OutOfBounds => {
+                    // solution space escaped
+                    break;
+                }
+            }
+        }
+
+        Ok(loops.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_pos() {
+        let map = include_str!("../../../inputs/6_test1.txt");
+        let guard = Guard::try_from(map).unwrap();
+        assert_eq!(guard.get_pos(), Vec2::new(4, 6));
+    }
+
+    #[test]
+    fn part1_step() {
+        let map = include_str!("../../../inputs/6_test1.txt");
+        let mut guard = Guard::try_from(map).unwrap();
+        let _ = guard.step();
+        assert_eq!(guard.get_pos(), Vec2::new(4, 5));
+    }
+
+    #[test]
+    fn part1_rotate() {
+        let map = include_str!("../../../inputs/6_test1_urotate.txt");
+        let mut guard = Guard::try_from(map).unwrap();
+        assert_eq!(guard.get_pos(), Vec2::new(4, 1));
+        let _ = guard.step();
+        assert_eq!(guard.get_pos(), Vec2::new(4, 1));
+        let _ = guard.step();
+        assert_eq!(guard.get_pos(), Vec2::new(5, 1));
+    }
+}
\ No newline at end of file
@@ -1,4 +1,7 @@
 use std::fmt::{Debug, Formatter};
+use anyhow::{bail, Context};
+use crate::input::parse_lines;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 #[derive(Clone)]
@@ -48,12 +51,21 @@ impl Term {
 }
 
 struct Equation(i64, Vec<i64>);
-impl From<&str> for Equation {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for Equation {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         let mut parts = s.split(":");
-        let first = parts.next().unwrap().parse().unwrap();
-        let second = parts.next().unwrap().split(" ").filter(|x| !x.is_empty()).map(|x| x.parse().unwrap()).collect();
-        Equation(first, second)
+        let first = parts.next().context("Missing test value")?.parse()?;
+        let second = parts.next().context("Missing ':' after test value")?
+            .split(" ")
+            .filter(|x| !x.is_empty())
+            .map(|x| x.parse())
+            .collect::<Result<Vec<i64>, _>>()?;
+        if second.is_empty() {
+            bail!("Missing numbers after ':'");
+        }
+        Ok(Equation(first, second))
     }
 }
 
@@ -117,30 +129,30 @@ pub struct BridgeRepair {
 }
 
 impl AocDay for BridgeRepair {
-    fn new(content: String) -> Self {
-        BridgeRepair {
-            equations: content.lines().map(|x| Equation::from(x)).collect()
-        }
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(BridgeRepair {
+            equations: parse_lines(&content, |_, x| Equation::try_from(x))?
+        })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut sum = 0;
         for equation in &self.equations {
             if let Some(_) = equation.solve(false) {
                 sum += equation.0;
             }
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let mut sum = 0;
         for equation in &self.equations {
             if let Some(_) = equation.solve(true) {
                 sum += equation.0;
             }
         }
-        sum.to_string()
+        Ok(sum.to_string())
     }
 }
 
@@ -150,22 +162,22 @@ mod tests {
 
     #[test]
     fn test_parse_equation() {
-        let equation = Equation::from("1: 2 3 4");
+        let equation = Equation::try_from("1: 2 3 4").unwrap();
         assert_eq!(equation.0, 1);
         assert_eq!(equation.1, vec![2, 3, 4]);
     }
 
     #[test]
     fn test_solve_equation() {
-        assert_eq!(Equation::from("5: 1 2 2").solve(false), Some(1));
-        assert_eq!(Equation::from("8: 4 2").solve(false), Some(1));
-        assert_eq!(Equation::from("16: 8 4 2").solve(false), None);
-        assert_eq!(Equation::from("16: 4 2 8").solve(false), Some(1));
+        assert_eq!(Equation::try_from("5: 1 2 2").unwrap().solve(false), Some(1));
+        assert_eq!(Equation::try_from("8: 4 2").unwrap().solve(false), Some(1));
+        assert_eq!(Equation::try_from("16: 8 4 2").unwrap().solve(false), None);
+        assert_eq!(Equation::try_from("16: 4 2 8").unwrap().solve(false), Some(1));
     }
 
     #[test]
     fn test_to_terms() {
-        let equation = Equation::from("5: 1 2 2");
+        let equation = Equation::try_from("5: 1 2 2").unwrap();
         let terms = equation.solve_to_terms(false);
         for term in terms.iter() {
             println!("{:?}", term);
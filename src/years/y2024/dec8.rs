@@ -1,41 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
-use std::ops::{Add, Sub};
+use anyhow::Context;
+use crate::params::Params;
 use crate::runner::AocDay;
-
-#[derive(PartialOrd, PartialEq, Copy, Clone, Eq, Hash)]
-struct Vec2 {
-    x: isize,
-    y: isize,
-}
-
-impl Debug for Vec2 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-impl Vec2 {
-    fn new(x: isize, y: isize) -> Self {
-        Vec2 { x, y }
-    }
-}
-
-impl Add for Vec2 {
-    type Output = Vec2;
-
-    fn add(self, rhs: Vec2) -> Self::Output {
-        Vec2 { x: self.x + rhs.x, y: self.y + rhs.y }
-    }
-}
-
-impl Sub for Vec2 {
-    type Output = Vec2;
-
-    fn sub(self, rhs: Vec2) -> Self::Output {
-        Vec2 { x: self.x - rhs.x, y: self.y - rhs.y }
-    }
-}
+use crate::utils::Vec2;
 
 #[derive(Clone)]
 struct Map {
@@ -47,9 +15,9 @@ struct Map {
 }
 
 impl Map {
-    fn all_from(string: &str) -> Vec<Self> {
+    fn all_from(string: &str) -> anyhow::Result<Vec<Self>> {
         let mut maps = HashMap::new();
-        let width = string.lines().next().unwrap().len();
+        let width = string.lines().next().context("Empty map")?.len();
         let height = string.lines().count();
         for (y, line) in string.lines().enumerate() {
             for (x, frequency) in line.chars().enumerate() {
@@ -68,7 +36,7 @@ impl Map {
             }
         }
 
-        maps.into_values().collect()
+        Ok(maps.into_values().collect())
     }
 
     fn is_in_bounds(&self, pos: Vec2) -> bool {
@@ -83,8 +51,8 @@ impl Map {
                 }
 
                 let diff = *node_a - *node_b;
-                let a_vec: Vec2 = (*node_a).into();
-                let b_vec: Vec2 = (*node_b).into();
+                let a_vec = *node_a;
+                let b_vec = *node_b;
                 let mut next_pos = a_vec + diff;
                 if any {
                     self.antinodes.insert(a_vec);
@@ -129,13 +97,13 @@ pub struct ResonantCollinearity {
 }
 
 impl AocDay for ResonantCollinearity {
-    fn new(content: String) -> Self {
-        ResonantCollinearity {
-            maps: Map::all_from(&content),
-        }
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
+        Ok(ResonantCollinearity {
+            maps: Map::all_from(&content)?,
+        })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut maps = self.maps.clone();
         let mut antinodes: HashSet<Vec2> = HashSet::new();
         for map in &mut maps {
@@ -143,10 +111,10 @@ impl AocDay for ResonantCollinearity {
             antinodes.extend(map.antinodes.iter());
         }
 
-        antinodes.len().to_string()
+        Ok(antinodes.len().to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let mut maps = self.maps.clone();
         let mut antinodes: HashSet<Vec2> = HashSet::new();
         for map in &mut maps {
@@ -154,7 +122,7 @@ impl AocDay for ResonantCollinearity {
             antinodes.extend(map.antinodes.iter());
         }
 
-        antinodes.len().to_string()
+        Ok(antinodes.len().to_string())
     }
 }
 
@@ -164,8 +132,8 @@ mod tests {
 
     #[test]
     fn parsing() {
-        let content = include_str!("../../inputs/8_test1_small.txt").to_string();
-        let maps = Map::all_from(&content);
+        let content = include_str!("../../../inputs/8_test1_small.txt").to_string();
+        let maps = Map::all_from(&content).unwrap();
         assert_eq!(maps.len(), 1);
         let map = &maps[0];
         assert_eq!(map.frequency, 'a');
@@ -173,8 +141,8 @@ mod tests {
 
     #[test]
     fn antinode_two() {
-        let content = include_str!("../../inputs/8_test1_small.txt").to_string();
-        let mut maps = Map::all_from(&content);
+        let content = include_str!("../../../inputs/8_test1_small.txt").to_string();
+        let mut maps = Map::all_from(&content).unwrap();
         let mut map = maps.pop().unwrap();
         map.calculate_antinodes(false);
         println!("{:?}", map);
@@ -185,8 +153,8 @@ mod tests {
 
     #[test]
     fn antinode_three() {
-        let content = include_str!("../../inputs/8_test1_large.txt").to_string();
-        let mut maps = Map::all_from(&content);
+        let content = include_str!("../../../inputs/8_test1_large.txt").to_string();
+        let mut maps = Map::all_from(&content).unwrap();
         let mut map = maps.pop().unwrap();
         map.calculate_antinodes(false);
         assert_eq!(map.antinodes.len(), 4);
@@ -199,8 +167,8 @@ mod tests {
 
     #[test]
     fn antinode_any_three() {
-        let content = include_str!("../../inputs/8_test2_small.txt").to_string();
-        let mut maps = Map::all_from(&content);
+        let content = include_str!("../../../inputs/8_test2_small.txt").to_string();
+        let mut maps = Map::all_from(&content).unwrap();
         let mut map = maps.pop().unwrap();
         map.calculate_antinodes(true);
         assert_eq!(map.antinodes.len(), 9);
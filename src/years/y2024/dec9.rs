@@ -1,4 +1,8 @@
 use std::fmt::{Debug, Formatter};
+use anyhow::Context;
+use crate::cancel::CancelToken;
+use crate::log::Progress;
+use crate::params::Params;
 use crate::runner::AocDay;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -38,12 +42,12 @@ impl Fragments {
         }
     }
 
-    fn densify_fast(&mut self) {
+    fn densify_fast(&mut self, cancel: &CancelToken) -> anyhow::Result<()> {
         let max_iters = self.0.len();
+        let mut progress = Progress::new("Compacting blocks", max_iters);
         for i in 0..max_iters {
-            if i % 1000 == 0 {
-                println!("Iteration {}/{}", i, max_iters);
-            }
+            cancel.check()?;
+            progress.set(i);
             let last_used = self.0.iter().rposition(|f| matches!(f, DiskFragment::Used { .. })).unwrap();
             let first_free = self.0.iter().position(|f| matches!(f, DiskFragment::Free)).unwrap();
             if last_used < first_free {
@@ -51,14 +55,15 @@ impl Fragments {
             }
             self.0.swap(last_used, first_free);
         }
+        Ok(())
     }
 
-    fn densify_blocks(&mut self) {
+    fn densify_blocks(&mut self, cancel: &CancelToken) -> anyhow::Result<()> {
         let max_id = self.1;
+        let mut progress = Progress::new("Moving files", max_id);
         for id in (0..max_id).rev() {
-            if id % 1000 == 0 {
-                println!("Iteration {}/{}", max_id - id, max_id);
-            }
+            cancel.check()?;
+            progress.set(max_id - id);
             let used_frag = DiskFragment::Used { id };
             let first_pos = self.0.iter().position(|f| f == &used_frag).unwrap();
             let last_pos = self.0.iter().rposition(|f| f == &used_frag).unwrap();
@@ -89,6 +94,7 @@ impl Fragments {
                 }
             }
         }
+        Ok(())
     }
 
     fn checksum(&self) -> i64 {
@@ -122,13 +128,13 @@ pub struct DiskFragmenter {
 }
 
 impl AocDay for DiskFragmenter {
-    fn new(content: String) -> Self {
+    fn new(content: String, _params: &Params) -> anyhow::Result<Self> {
         let mut fragments = Vec::new();
         let mut id = 0;
         let mut last_space = true;
 
-        for c in content.as_str().chars() {
-            let width: usize = c.to_string().parse().unwrap();
+        for (i, c) in content.trim_end().chars().enumerate() {
+            let width = c.to_digit(10).with_context(|| format!("Invalid digit '{}' at position {}", c, i + 1))?;
             if last_space {
                 for _ in 0..width {
                     fragments.push(DiskFragment::Used { id });
@@ -142,32 +148,32 @@ impl AocDay for DiskFragmenter {
             last_space = !last_space;
         }
 
-        Self { fragments: Fragments(fragments, id) }
+        Ok(Self { fragments: Fragments(fragments, id) })
     }
 
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<String> {
         let mut fragments = self.fragments.clone();
-        fragments.densify_fast();
-        fragments.checksum().to_string()
+        fragments.densify_fast(&CancelToken::current())?;
+        Ok(fragments.checksum().to_string())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<String> {
         let mut fragments = self.fragments.clone();
-        fragments.densify_blocks();
-        fragments.checksum().to_string()
+        fragments.densify_blocks(&CancelToken::current())?;
+        Ok(fragments.checksum().to_string())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bench::measure;
     use crate::runner::AocDay;
-    use test::{Bencher, black_box};
 
     #[test]
     fn test_parse() {
         let content = "12345";
-        let disk_fragmenter = DiskFragmenter::new(content.to_string());
+        let disk_fragmenter = DiskFragmenter::new(content.to_string(), &Params::default()).unwrap();
         assert_eq!(disk_fragmenter.fragments.0.len(), "0..111....22222".len());
         assert!(matches!(disk_fragmenter.fragments.0[0], DiskFragment::Used { .. }));
         assert!(matches!(disk_fragmenter.fragments.0[1], DiskFragment::Free));
@@ -177,65 +183,63 @@ mod tests {
 
     #[test]
     fn test_dense() {
-        let not_dense = DiskFragmenter::new("12345".to_string());
+        let not_dense = DiskFragmenter::new("12345".to_string(), &Params::default()).unwrap();
         assert!(!not_dense.fragments.is_dense());
 
-        let dense = DiskFragmenter::new("90909".to_string());
+        let dense = DiskFragmenter::new("90909".to_string(), &Params::default()).unwrap();
         assert!(dense.fragments.is_dense());
     }
 
     #[test]
     fn test_densify() {
-        let mut not_dense = DiskFragmenter::new("12345".to_string());
+        let mut not_dense = DiskFragmenter::new("12345".to_string(), &Params::default()).unwrap();
         not_dense.fragments.densify();
         assert!(not_dense.fragments.is_dense());
     }
 
     #[test]
     fn test_densify_fast() {
-        let mut not_dense = DiskFragmenter::new("12345".to_string());
-        not_dense.fragments.densify_fast();
+        let mut not_dense = DiskFragmenter::new("12345".to_string(), &Params::default()).unwrap();
+        not_dense.fragments.densify_fast(&CancelToken::new()).unwrap();
         assert!(not_dense.fragments.is_dense());
     }
 
-    #[bench]
-    fn bench_densify(b: &mut Bencher) {
-        let not_dense = DiskFragmenter::new("12345".to_string());
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn bench_densify() {
+        let not_dense = DiskFragmenter::new("12345".to_string(), &Params::default()).unwrap();
 
-        b.iter(|| {
-            let mut fragments = not_dense.fragments.clone();
-            black_box(fragments.densify());
-        });
+        let stats = measure(100, 10_000, || not_dense.fragments.clone(), |mut fragments| fragments.densify());
+        println!("densify: {:?}", stats);
     }
 
-    #[bench]
-    fn bench_densify_fast(b: &mut Bencher) {
-        let not_dense = DiskFragmenter::new("12345".to_string());
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn bench_densify_fast() {
+        let not_dense = DiskFragmenter::new("12345".to_string(), &Params::default()).unwrap();
 
-        b.iter(|| {
-            let mut fragments = not_dense.fragments.clone();
-            black_box(fragments.densify_fast());
-        });
+        let stats = measure(100, 10_000, || not_dense.fragments.clone(), |mut fragments| fragments.densify_fast(&CancelToken::new()));
+        println!("densify_fast: {:?}", stats);
     }
 
     #[test]
     fn test_densify_blocks() {
-        let mut disk_fragmenter = DiskFragmenter::new("90909".to_string());
-        disk_fragmenter.fragments.densify_blocks();
+        let mut disk_fragmenter = DiskFragmenter::new("90909".to_string(), &Params::default()).unwrap();
+        disk_fragmenter.fragments.densify_blocks(&CancelToken::new()).unwrap();
         assert_eq!(disk_fragmenter.fragments.checksum(), 513);
 
-        let mut disk_fragmenter = DiskFragmenter::new("133".to_string());
-        disk_fragmenter.fragments.densify_blocks();
+        let mut disk_fragmenter = DiskFragmenter::new("133".to_string(), &Params::default()).unwrap();
+        disk_fragmenter.fragments.densify_blocks(&CancelToken::new()).unwrap();
         assert_eq!(disk_fragmenter.fragments.checksum(), 6);
 
-        let mut disk_fragmenter = DiskFragmenter::new("12302".to_string());
-        disk_fragmenter.fragments.densify_blocks();
+        let mut disk_fragmenter = DiskFragmenter::new("12302".to_string(), &Params::default()).unwrap();
+        disk_fragmenter.fragments.densify_blocks(&CancelToken::new()).unwrap();
         assert_eq!(disk_fragmenter.fragments.checksum(), 18);
     }
 
     #[test]
     fn test_checksum() {
-        let mut disk_fragmenter = DiskFragmenter::new("123".to_string());
+        let mut disk_fragmenter = DiskFragmenter::new("123".to_string(), &Params::default()).unwrap();
         disk_fragmenter.fragments.densify();
         assert_eq!(disk_fragmenter.fragments.checksum(), 6);
     }